@@ -0,0 +1,296 @@
+
+use super::fabric::Claim;
+
+use std::collections::HashMap;
+
+// Bounds /////////////////////////////////////////////////////////////////////
+
+/*
+ An axis-aligned bounding box with exclusive right and bottom edges.
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Bounds {
+    left: i64,
+    top: i64,
+    right: i64,
+    bottom: i64,
+}
+
+impl Bounds {
+
+    fn of_claim(claim: &Claim) -> Bounds {
+        Bounds { left: claim.x, top: claim.y, right: claim.right(), bottom: claim.bottom() }
+    }
+
+    /*
+     Find the smallest bounds containing both bounds.
+    */
+    fn union(&self, other: &Bounds) -> Bounds {
+        Bounds {
+            left: self.left.min(other.left),
+            top: self.top.min(other.top),
+            right: self.right.max(other.right),
+            bottom: self.bottom.max(other.bottom),
+        }
+    }
+
+    fn intersects(&self, other: &Bounds) -> bool {
+        self.left < other.right && other.left < self.right &&
+        self.top < other.bottom && other.top < self.bottom
+    }
+
+    fn contains(&self, x: i64, y: i64) -> bool {
+        self.left <= x && x < self.right && self.top <= y && y < self.bottom
+    }
+
+    /*
+     Get the center point, doubled to avoid rounding.
+    */
+    fn center_x2(&self) -> (i64, i64) {
+        (self.left + self.right, self.top + self.bottom)
+    }
+}
+
+// ClaimOverlap ///////////////////////////////////////////////////////////////
+
+/*
+ The area shared by a pair of overlapping claims.
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ClaimOverlap {
+    pub a: i32, // Lower ID
+    pub b: i32, // Higher ID
+    pub area: i64,
+}
+
+// ClaimIndex /////////////////////////////////////////////////////////////////
+
+const NODE_CAPACITY: usize = 16;
+
+/*
+ A node in the R-tree.
+ Entries refer to claims in a leaf, and to other nodes otherwise.
+*/
+struct Node {
+    bounds: Bounds,
+    is_leaf: bool,
+    entries: Vec<usize>,
+}
+
+/*
+ A spatial index over a set of claims, answering questions about which claims
+ overlap which others.
+
+ The index is a static R-tree bulk-loaded using Sort-Tile-Recursive packing;
+ at each level the entries are sorted into vertical slices by x, then each slice
+ is sorted by y and packed into nodes. Any query only descends into nodes whose
+ bounds intersect the area of interest.
+*/
+pub struct ClaimIndex {
+    claims: Vec<Claim>,
+    by_id: HashMap<i32, usize>,
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+impl ClaimIndex {
+
+    /*
+     Build an index over the given claims.
+     Claim IDs are assumed to be unique.
+    */
+    pub fn new(claims: &[Claim]) -> ClaimIndex {
+        let mut index = ClaimIndex {
+            claims: claims.to_vec(),
+            by_id: claims.iter()
+                .enumerate()
+                .map(|(i, c)| (c.id, i))
+                .collect(),
+            nodes: Vec::new(),
+            root: None,
+        };
+
+        // Pack claims into leaves, then pack nodes until only the root remains
+        let mut level = claims.iter()
+            .enumerate()
+            .map(|(i, c)| (Bounds::of_claim(c), i))
+            .collect::<Vec<(Bounds, usize)>>();
+        let mut is_leaf = true;
+        while !level.is_empty() {
+            level = index.pack(level, is_leaf);
+            is_leaf = false;
+            if level.len() == 1 {
+                index.root = Some(level[0].1);
+                break
+            }
+        }
+
+        index
+    }
+
+    /*
+     Pack a level of entries into nodes, returning the bounds and index of each node.
+    */
+    fn pack(&mut self, mut entries: Vec<(Bounds, usize)>, is_leaf: bool) -> Vec<(Bounds, usize)> {
+        let num_nodes = entries.len().div_ceil(NODE_CAPACITY);
+        let num_slices = (num_nodes as f64).sqrt().ceil() as usize;
+        let slice_size = num_slices * NODE_CAPACITY;
+
+        entries.sort_by_key(|(b, _)| b.center_x2().0);
+
+        let mut packed = Vec::with_capacity(num_nodes);
+        for slice in entries.chunks_mut(slice_size) {
+            slice.sort_by_key(|(b, _)| b.center_x2().1);
+            for group in slice.chunks(NODE_CAPACITY) {
+                let bounds = group.iter()
+                    .skip(1)
+                    .fold(group[0].0, |acc, (b, _)| acc.union(b));
+                self.nodes.push(Node {
+                    bounds,
+                    is_leaf,
+                    entries: group.iter().map(|(_, i)| *i).collect(),
+                });
+                packed.push((bounds, self.nodes.len() - 1));
+            }
+        }
+
+        packed
+    }
+
+    /*
+     Visit every claim whose bounds satisfy the predicate.
+     Only nodes whose bounds also satisfy the predicate are searched, so it must
+     hold for any bounds containing a matching claim.
+    */
+    fn search<'a, P, F>(&'a self, matches: P, mut visit: F)
+        where P: Fn(&Bounds) -> bool,
+              F: FnMut(&'a Claim)
+    {
+        let mut stack = self.root.into_iter().collect::<Vec<usize>>();
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if !matches(&node.bounds) { continue }
+
+            for &entry in &node.entries {
+                if node.is_leaf {
+                    let claim = &self.claims[entry];
+                    if matches(&Bounds::of_claim(claim)) { visit(claim) }
+                } else {
+                    stack.push(entry);
+                }
+            }
+        }
+    }
+
+    // Queries ////////////////////////////////////////////
+
+    /*
+     Get the claim with the given ID.
+    */
+    #[allow(dead_code)]
+    pub fn get(&self, id: i32) -> Option<&Claim> {
+        self.by_id.get(&id).map(|&i| &self.claims[i])
+    }
+
+    /*
+     Find all claims overlapping the claim with the given ID, along with the area of each overlap.
+     Returns None if there is no such claim.
+    */
+    #[allow(dead_code)]
+    pub fn overlapping(&self, id: i32) -> Option<Vec<(&Claim, i64)>> {
+        let claim = self.get(id)?;
+        let bounds = Bounds::of_claim(claim);
+
+        let mut found = Vec::new();
+        self.search(|b| b.intersects(&bounds), |other| {
+            if other.id != id {
+                found.push(other)
+            }
+        });
+        found.sort_by_key(|c| c.id);
+
+        Some(found.into_iter()
+            .map(|other| (other, claim.overlap_area(other)))
+            .collect())
+    }
+
+    /*
+     Find all claims covering the square inch at (x,y).
+    */
+    #[allow(dead_code)]
+    pub fn covering(&self, x: i64, y: i64) -> Vec<&Claim> {
+        let mut found = Vec::new();
+        self.search(|b| b.contains(x, y), |claim| found.push(claim));
+        found.sort_by_key(|c| c.id);
+        found
+    }
+
+    /*
+     Find every pair of overlapping claims, along with the area of each overlap.
+     Pairs are ordered by ID.
+    */
+    #[allow(dead_code)]
+    pub fn overlap_pairs(&self) -> Vec<ClaimOverlap> {
+        let mut pairs = Vec::new();
+        for claim in &self.claims {
+            let bounds = Bounds::of_claim(claim);
+            self.search(|b| b.intersects(&bounds), |other| {
+                if claim.id < other.id {
+                    pairs.push(ClaimOverlap {
+                        a: claim.id,
+                        b: other.id,
+                        area: claim.overlap_area(other),
+                    })
+                }
+            });
+        }
+
+        pairs.sort_by_key(|p| (p.a, p.b));
+        pairs
+    }
+
+    /*
+     Find groups of claims connected by overlap.
+     i.e. any two claims in a group are linked by a chain of overlapping claims.
+
+     Claims without any overlap form a group by themselves.
+     Groups are ordered from largest to smallest, and each is ordered by ID.
+    */
+    #[allow(dead_code)]
+    pub fn overlap_groups(&self) -> Vec<Vec<i32>> {
+
+        // Union-find over claim indices
+        let mut parent = (0..self.claims.len()).collect::<Vec<usize>>();
+        fn find(parent: &mut [usize], i: usize) -> usize {
+            let mut root = i;
+            while parent[root] != root { root = parent[root] }
+            let mut i = i;
+            while parent[i] != root {
+                let next = parent[i];
+                parent[i] = root;
+                i = next;
+            }
+            root
+        }
+
+        // Join overlapping claims
+        for pair in self.overlap_pairs() {
+            let a = find(&mut parent, self.by_id[&pair.a]);
+            let b = find(&mut parent, self.by_id[&pair.b]);
+            parent[a] = b;
+        }
+
+        // Collect groups
+        let mut groups: HashMap<usize, Vec<i32>> = HashMap::new();
+        for i in 0..self.claims.len() {
+            let root = find(&mut parent, i);
+            groups.entry(root).or_default().push(self.claims[i].id);
+        }
+
+        let mut groups = groups.into_values()
+            .map(|mut group| { group.sort(); group })
+            .collect::<Vec<Vec<i32>>>();
+        groups.sort_by(|a, b| b.len().cmp(&a.len()).then(a[0].cmp(&b[0])));
+        groups
+    }
+}
@@ -3,18 +3,82 @@
 
 /*
  Represents the input to a puzzle.
+ The file is only read once its contents are first needed, so puzzles which stream it
+ from path() never hold all of it in memory.
 */
 pub struct Input {
-    raw: String
+    path: String,
+    raw: std::cell::OnceCell<String>,
+    options: Vec<(String, String)>, // Runner options given on the command line, and their values
+    option_names: Vec<String>,      // Every runner option which may be given
 }
 
 impl Input {
 
     pub fn new(path: String) -> Input {
-        let raw = std::fs::read_to_string(path)
-            .unwrap();
+        Input { path, raw: std::cell::OnceCell::new(), options: Vec::new(), option_names: Vec::new() }
+    }
+
+    /*
+     Take the values of runner options from the command line, given as either
+     `--name value` or `--name=value`.
+    */
+    #[allow(dead_code)]
+    pub fn with_options(mut self, names: &[&str]) -> Input {
+        let mut args = std::env::args().skip(1);
+        while let Some(arg) = args.next() {
+            for name in names {
+                let flag = format!("--{}", name);
+                if arg == flag {
+                    let value = args.next()
+                        .unwrap_or_else(|| usage_error(&format!("Missing value for {}", flag), names));
+                    self.options.push((name.to_string(), value));
+                } else if arg.starts_with(&(flag.clone() + "=")) {
+                    self.options.push((name.to_string(), arg[flag.len() + 1..].to_string()));
+                }
+            }
+        }
+        self.option_names = names.iter().map(|name| name.to_string()).collect();
+        self
+    }
+
+    /*
+     Get the value of a runner option, if it was given.
+    */
+    #[allow(dead_code)]
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, value)| &value[..])
+    }
 
-        Input { raw:raw }
+    /*
+     Get the value of a runner option as another type (such as a number), if it was given.
+    */
+    #[allow(dead_code)]
+    pub fn parse_option<T: std::str::FromStr>(&self, name: &str) -> Option<T> {
+        self.option(name).map(|value| {
+            value.parse().unwrap_or_else(|_| {
+                let names = self.option_names.iter().map(|n| &n[..]).collect::<Vec<&str>>();
+                usage_error(&format!("Invalid value for --{}: {}", name, value), &names)
+            })
+        })
+    }
+
+    /*
+     Check whether any runner options were given. These aren't understood by the benchmark,
+     so it's skipped.
+    */
+    pub fn has_options(&self) -> bool {
+        !self.options.is_empty()
+    }
+
+    /*
+     Get the path the input was read from, e.g. to stream it again.
+    */
+    #[allow(dead_code)]
+    pub fn path(&self) -> &str {
+        &self.path
     }
 
     /*
@@ -22,7 +86,10 @@ impl Input {
     */
     #[allow(dead_code)]
     pub fn raw(&self) -> &String {
-        &self.raw
+        self.raw.get_or_init(|| {
+            std::fs::read_to_string(&self.path)
+                .unwrap()
+        })
     }
 
     /*
@@ -30,27 +97,77 @@ impl Input {
     */
     #[allow(dead_code)]
     pub fn to_lines(&self) -> Vec<String> {
-        self.raw.lines()
+        self.raw().lines()
             .map(String::from)
             .collect()
     }
 }
 
+/*
+ Report a mistake in the runner options given on the command line, then exit.
+*/
+fn usage_error(message: &str, names: &[&str]) -> ! {
+    let program = std::env::args().next().unwrap_or_default();
+    let options = names.iter()
+        .map(|name| format!("[--{} <value>]", name))
+        .collect::<Vec<String>>();
+    eprintln!("{}", message);
+    eprintln!("Usage: {} {}", program, options.join(" "));
+    std::process::exit(2)
+}
+
 // Harness ////////////////////////////////////////////////////////////////////
 
+/*
+ Configure the benchmark, taking any overrides from the command line.
+*/
+#[allow(dead_code)]
+pub fn criterion() -> criterion::Criterion {
+    criterion::Criterion::default()
+        .warm_up_time(std::time::Duration::new(2,0))
+        .measurement_time(std::time::Duration::new(5, 0))
+        .sample_size(10)
+        .configure_from_args()
+}
+
+/*
+ Run a puzzle, then benchmark it. e.g.
+     run! {
+         input = "day1",
+         options = ["export"], // Optional, e.g. `cargo run --bin day1 -- --export out.txt`
+         run = |input: &Input| { ... },
+         bench = |input: &Input| { ... }
+     }
+
+ If any runner options are given, only the run section is executed.
+*/
 macro_rules! run {
-    (input = $input:expr, run = $f_run:expr, bench = $f_bench:expr) => {
+    // Compare multiple named functions against the same input
+    (input = $input:expr, $(options = [$($option:expr),* $(,)*],)* run = $f_run:expr, bench = { $($name:expr => $f_bench:expr),+ $(,)* }) => {
+        run!(@main input = $input, $(options = [$($option),*],)* run = $f_run);
 
-        fn main_run(input: &Input) {
-            $f_run(&input);
+        fn main_bench(input: Input) {
+            let mut criterion = common::criterion();
+
+            let input = std::rc::Rc::new(input);
+            $(
+                let bench_input = input.clone();
+                criterion.bench_function(&[file!(), $name].join(": "), move |b| {
+                    b.iter(|| {
+                        $f_bench(&*bench_input);
+                    })
+                });
+            )+
+
+            criterion.final_summary();
         }
+    };
+
+    (input = $input:expr, $(options = [$($option:expr),* $(,)*],)* run = $f_run:expr, bench = $f_bench:expr) => {
+        run!(@main input = $input, $(options = [$($option),*],)* run = $f_run);
 
         fn main_bench(input: Input) {
-            let mut criterion = criterion::Criterion::default()
-                .warm_up_time(std::time::Duration::new(2,0))
-                .measurement_time(std::time::Duration::new(5, 0))
-                .sample_size(10)
-                .configure_from_args();
+            let mut criterion = common::criterion();
 
             criterion.bench_function(file!(), move |b| {
                 b.iter(|| {
@@ -60,18 +177,30 @@ macro_rules! run {
 
             criterion.final_summary();
         }
+    };
+
+    // Shared by both of the above; expects main_bench to be defined alongside
+    (@main input = $input:expr, $(options = [$($option:expr),*],)* run = $f_run:expr) => {
+
+        fn main_run(input: &Input) {
+            $f_run(&input);
+        }
 
         fn main() {
 
             // Setup
             let path = ["res/input/", $input, ".txt"].join("");
-            let input = common::Input::new(path);
+            let input = common::Input::new(path)
+                $(.with_options(&[$($option),*]))*;
 
             println!("\n======== RUN ========\n");
             main_run(&input);
+            if input.has_options() {
+                return
+            }
 
             println!("\n======== BENCH ========\n");
             main_bench(input);
         }
-    }
-}
\ No newline at end of file
+    };
+}
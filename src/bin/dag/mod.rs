@@ -0,0 +1,832 @@
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// Errors /////////////////////////////////////////////////////////////////////
+
+/*
+ A reason a graph can't be scheduled.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub enum DagError<N> {
+    Cycle(Vec<N>),                                  // Each node must be completed before the next, and the last before the first
+    SelfDependency(N),                              // A node which depends on itself
+    DuplicateDependency { node: N, dependency: N }, // The same dependency listed more than once
+    Unassignable(N),                                // A node which no worker is able to work on
+}
+
+impl<N: Display> Display for DagError<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DagError::Cycle(nodes) => {
+                let path = nodes.iter()
+                    .chain(nodes.first())
+                    .map(|n| n.to_string())
+                    .collect::<Vec<String>>();
+                write!(f, "Cycle: {}", path.join(" -> "))
+            },
+            DagError::SelfDependency(node) => write!(f, "Self-dependency: {} depends on itself", node),
+            DagError::DuplicateDependency { node, dependency } =>
+                write!(f, "Duplicate dependency: {} depends on {} more than once", node, dependency),
+            DagError::Unassignable(node) => write!(f, "Unassignable: no worker can work on {}", node),
+        }
+    }
+}
+
+/*
+ Every reason a graph can't be scheduled.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidDag<N>(pub Vec<DagError<N>>);
+
+impl<N: Display> Display for InvalidDag<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Invalid graph:")?;
+        for error in &self.0 {
+            write!(f, "\n\t{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+// Dag ////////////////////////////////////////////////////////////////////////
+
+/*
+ A directed acyclic graph of nodes which depend on other nodes.
+ e.g. steps which can't begin until the steps they depend on are complete.
+
+ Nodes may be any ordered type, such as single letters, names, or numbers.
+*/
+pub struct Dag<N> {
+    dependencies: BTreeMap<N, Vec<N>>,
+}
+
+impl<N: Ord + Clone> Dag<N> {
+
+    pub fn new() -> Dag<N> {
+        Dag { dependencies: BTreeMap::new() }
+    }
+
+    /*
+     Add a node without any dependencies, if it doesn't already exist.
+    */
+    pub fn add_node(&mut self, node: N) {
+        self.dependencies.entry(node).or_default();
+    }
+
+    /*
+     Add a dependency to a node, adding either if they don't already exist.
+    */
+    pub fn add_dependency(&mut self, node: N, dependency: N) {
+        self.add_node(dependency.clone());
+        self.dependencies.entry(node)
+            .or_default()
+            .push(dependency);
+    }
+
+    /*
+     Get every node in order.
+    */
+    #[allow(dead_code)]
+    pub fn nodes(&self) -> impl Iterator<Item=&N> {
+        self.dependencies.keys()
+    }
+
+    /*
+     Get the nodes which a node directly depends on.
+    */
+    #[allow(dead_code)]
+    pub fn dependencies(&self, node: &N) -> &[N] {
+        self.dependencies.get(node).map(|d| &d[..]).unwrap_or(&[])
+    }
+
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.dependencies.len()
+    }
+
+    // Validation /////////////////////////////////////////
+
+    /*
+     Check that the graph can be scheduled, finding every self-dependency and duplicate
+     dependency, and any cycles. Each cycle starts at its lowest node.
+    */
+    pub fn validate(&self) -> Result<(), InvalidDag<N>> {
+        let (nodes, dependents, _) = self.index();
+        self.validate_indexed(&nodes, &dependents)
+    }
+
+    /*
+     Same as validate(), using the graph already numbered by index().
+    */
+    fn validate_indexed(&self, nodes: &[&N], dependents: &[Vec<usize>]) -> Result<(), InvalidDag<N>> {
+        let mut errors = Vec::new();
+
+        for (node, dependencies) in &self.dependencies {
+            for (i, dependency) in dependencies.iter().enumerate() {
+                if dependencies[..i].contains(dependency) {
+                    continue // Already reported
+                } else if dependency == node {
+                    errors.push(DagError::SelfDependency(node.clone()));
+                } else if dependencies[i + 1..].contains(dependency) {
+                    let (node, dependency) = (node.clone(), dependency.clone());
+                    errors.push(DagError::DuplicateDependency { node, dependency });
+                }
+            }
+        }
+
+        errors.extend(Dag::find_cycles(nodes, dependents).into_iter().map(DagError::Cycle));
+
+        if errors.is_empty() { Ok(()) } else { Err(InvalidDag(errors)) }
+    }
+
+    /*
+     Find cycles by following dependents depth-first, where reaching a node which is
+     still on the current path closes a cycle. Self-dependencies are ignored.
+     Not every cycle is found, but at least one is if any exist.
+    */
+    fn find_cycles(nodes: &[&N], dependents: &[Vec<usize>]) -> Vec<Vec<N>> {
+        const UNVISITED: u8 = 0;
+        const ON_PATH: u8 = 1;
+        const DONE: u8 = 2;
+
+        let mut state = vec![UNVISITED; nodes.len()];
+        let mut cycles = Vec::new();
+        for start in 0..nodes.len() {
+            if state[start] != UNVISITED { continue }
+
+            // Path of nodes, and the index of the next dependent to follow from each
+            let mut path: Vec<(usize, usize)> = vec![(start, 0)];
+            state[start] = ON_PATH;
+            while let Some((node, next)) = path.last_mut() {
+                let node = *node;
+                let dependent = match dependents[node].get(*next) {
+                    Some(&dependent) => dependent,
+                    None => {
+                        state[node] = DONE;
+                        path.pop();
+                        continue
+                    }
+                };
+                *next += 1;
+
+                if state[dependent] == UNVISITED {
+                    state[dependent] = ON_PATH;
+                    path.push((dependent, 0));
+                } else if state[dependent] == ON_PATH && dependent != node {
+                    // Each node on the path must be completed before the next
+                    let from = path.iter().position(|&(n, _)| n == dependent).unwrap();
+                    let mut cycle = path[from..].iter()
+                        .map(|&(n, _)| nodes[n].clone())
+                        .collect::<Vec<N>>();
+                    let lowest = (0..cycle.len()).min_by_key(|&i| &cycle[i]).unwrap();
+                    cycle.rotate_left(lowest);
+                    if !cycles.contains(&cycle) {
+                        cycles.push(cycle); // Duplicate dependencies close the same cycle again
+                    }
+                }
+            }
+        }
+        cycles
+    }
+
+    // Ordering ///////////////////////////////////////////
+
+    /*
+     Number every node in order, and find the nodes which depend on each (by number),
+     and how many dependencies each has.
+    */
+    fn index(&self) -> (Vec<&N>, Vec<Vec<usize>>, Vec<usize>) {
+        let nodes = self.dependencies.keys().collect::<Vec<&N>>();
+        let mut dependents = vec![Vec::new(); nodes.len()];
+        let mut indegree = vec![0; nodes.len()];
+
+        for (i, dependencies) in self.dependencies.values().enumerate() {
+            for dependency in dependencies {
+                let d = nodes.binary_search(&dependency).unwrap(); // Every dependency is also a node
+                dependents[d].push(i);
+                indegree[i] += 1;
+            }
+        }
+
+        (nodes, dependents, indegree)
+    }
+
+    /*
+     Find the order in which nodes must be completed, based on dependencies.
+     Where multiple nodes are available, the one with the lowest priority key is chosen first.
+
+     This uses Kahn's algorithm, counting the remaining dependencies of each node. Nodes with
+     none are kept in a heap ordered by priority, and completing a node decrements the count
+     of each node depending on it; adding those which reach zero to the heap.
+    */
+    pub fn order_by<K, F>(&self, priority: F) -> Result<Vec<N>, InvalidDag<N>>
+        where K: Ord,
+              F: Fn(&N) -> K
+    {
+        let (nodes, dependents, mut indegree) = self.index();
+        self.validate_indexed(&nodes, &dependents)?;
+
+        let mut ready = (0..nodes.len())
+            .filter(|&i| indegree[i] == 0)
+            .map(|i| Reverse((priority(nodes[i]), i)))
+            .collect::<BinaryHeap<_>>();
+
+        let mut order = Vec::with_capacity(nodes.len());
+        while let Some(Reverse((_, i))) = ready.pop() {
+
+            // Mark node as complete
+            order.push(nodes[i].clone());
+            for &dependent in &dependents[i] {
+                indegree[dependent] -= 1;
+                if indegree[dependent] == 0 {
+                    ready.push(Reverse((priority(nodes[dependent]), dependent)));
+                }
+            }
+        }
+        Ok(order)
+    }
+
+    /*
+     Find the order in which nodes must be completed, with ties broken by the nodes' ordering.
+    */
+    #[allow(dead_code)]
+    pub fn order(&self) -> Result<Vec<N>, InvalidDag<N>> {
+        self.order_by(|_| ())
+    }
+
+    // Simulation /////////////////////////////////////////
+
+    /*
+     Find when and by which worker each node is completed.
+
+     A number of identical workers can operate in parallel, each taking the available node with
+     the lowest priority key as soon as they're free, and each node can take a different duration.
+     Where multiple workers are free, the lowest numbered takes the node. At least one worker
+     is always used.
+    */
+    pub fn schedule<K, D, F>(&self, workers: usize, duration: D, priority: F) -> Result<Schedule<N>, InvalidDag<N>>
+        where K: Ord,
+              D: Fn(&N) -> usize,
+              F: Fn(&N) -> K
+    {
+        self.schedule_with(&vec![Worker::new(); workers.max(1)], duration, priority)
+    }
+
+    /*
+     Same as schedule(), except each worker may have a different speed, and may only be able to
+     work on some nodes. Available nodes are taken in order of priority, each by the fastest free
+     worker able to work on it (or the lowest numbered of those). Nodes which no free worker can
+     work on wait until one is free.
+
+     Available nodes are found with Kahn's algorithm as in order_by(), and nodes being worked
+     on are kept in a second heap ordered by when they will be complete.
+    */
+    pub fn schedule_with<K, D, F>(&self, workers: &[Worker<N>], duration: D, priority: F) -> Result<Schedule<N>, InvalidDag<N>>
+        where K: Ord,
+              D: Fn(&N) -> usize,
+              F: Fn(&N) -> K
+    {
+        let (nodes, dependents, mut indegree) = self.index();
+        let mut errors = match self.validate_indexed(&nodes, &dependents) {
+            Ok(()) => Vec::new(),
+            Err(InvalidDag(errors)) => errors,
+        };
+        errors.extend(nodes.iter()
+            .filter(|node| !workers.iter().any(|w| w.can_work_on(node)))
+            .map(|node| DagError::Unassignable((*node).clone())));
+        if !errors.is_empty() {
+            return Err(InvalidDag(errors))
+        }
+
+        let mut time = 0;
+        let mut idle = (0..workers.len()).collect::<BTreeSet<usize>>();
+        let mut tasks = Vec::with_capacity(nodes.len());
+
+        // Nodes are either waiting, ready to be worked on, being worked on, or complete
+        let mut ready = (0..nodes.len())
+            .filter(|&i| indegree[i] == 0)
+            .map(|i| Reverse((priority(nodes[i]), i)))
+            .collect::<BinaryHeap<_>>();
+        let mut working = BinaryHeap::new();
+
+        loop {
+
+            // Assign nodes to idle workers able to work on them
+            let mut unassigned = Vec::new();
+            while !idle.is_empty() {
+                let next = match ready.pop() {
+                    Some(next) => next,
+                    None => break, // No more work available right now
+                };
+                let Reverse((_, i)) = next;
+                let worker = idle.iter()
+                    .filter(|&&w| workers[w].can_work_on(nodes[i]))
+                    .min_by_key(|&&w| (Reverse(workers[w].speed), w))
+                    .cloned();
+                match worker {
+                    Some(worker) => {
+                        idle.remove(&worker);
+                        let end = time + workers[worker].time_for(duration(nodes[i]));
+                        tasks.push(Task { node: nodes[i].clone(), worker, start: time, end });
+                        working.push(Reverse((end, i, worker)));
+                    },
+                    None => unassigned.push(next), // Wait for a worker able to work on it
+                }
+            }
+            ready.extend(unassigned);
+
+            // Jump to time of next node completion
+            let Reverse((complete_at, i, worker)) = match working.pop() {
+                Some(next) => next,
+                None => break,
+            };
+            for &dependent in &dependents[i] {
+                indegree[dependent] -= 1;
+                if indegree[dependent] == 0 {
+                    ready.push(Reverse((priority(nodes[dependent]), dependent)));
+                }
+            }
+            time = complete_at;
+            idle.insert(worker);
+        }
+
+        Ok(Schedule { workers: workers.len(), tasks })
+    }
+
+    /*
+     Find how urgently each node should be worked on under a dispatch policy, where nodes with
+     a higher urgency are taken first. This can be used as a priority key, e.g.
+         let urgency = dag.urgency(Dispatch::LongestFirst, &duration)?;
+         dag.schedule(workers, &duration, |n| Reverse(urgency[n]))
+    */
+    #[allow(dead_code)]
+    pub fn urgency<D>(&self, policy: Dispatch, duration: D) -> Result<BTreeMap<N, usize>, InvalidDag<N>>
+        where D: Fn(&N) -> usize
+    {
+        match policy {
+            Dispatch::Alphabetical => {
+                self.validate()?;
+                Ok(self.nodes().map(|n| (n.clone(), 0)).collect())
+            },
+            Dispatch::LongestFirst => {
+                self.validate()?;
+                Ok(self.nodes().map(|n| (n.clone(), duration(n))).collect())
+            },
+            Dispatch::CriticalPathFirst => {
+                // Longest chain from each node to the end, found in reverse topological order
+                let order = self.order()?;
+                let (nodes, dependents, _) = self.index();
+                let mut remaining = vec![0; nodes.len()];
+                for node in order.iter().rev() {
+                    let i = nodes.binary_search(&node).unwrap();
+                    let after = dependents[i].iter().map(|&d| remaining[d]).max().unwrap_or(0);
+                    remaining[i] = duration(node) + after;
+                }
+                Ok(nodes.into_iter().cloned().zip(remaining).collect())
+            },
+        }
+    }
+
+    /*
+     Find the total time taken to complete every node, scheduled as in schedule().
+    */
+    #[allow(dead_code)]
+    pub fn simulate<K, D, F>(&self, workers: usize, duration: D, priority: F) -> Result<usize, InvalidDag<N>>
+        where K: Ord,
+              D: Fn(&N) -> usize,
+              F: Fn(&N) -> K
+    {
+        self.schedule(workers, duration, priority).map(|s| s.duration())
+    }
+
+    /*
+     Find the longest chain of dependent nodes by total duration, and its duration.
+
+     No schedule can be shorter than this, even with unlimited workers; each node must wait
+     for the chain before it. The chain ending at each node is found in topological order by
+     extending the longest chain ending at any of its dependencies.
+    */
+    #[allow(dead_code)]
+    pub fn critical_path<D>(&self, duration: D) -> Result<(Vec<N>, usize), InvalidDag<N>>
+        where D: Fn(&N) -> usize
+    {
+        let order = self.order()?;
+        let mut longest: BTreeMap<&N, (usize, Option<&N>)> = BTreeMap::new(); // Finish time and previous node
+        for node in &order {
+            let previous = self.dependencies(node).iter()
+                .max_by_key(|d| (longest[d].0, Reverse(*d)));
+            let start = previous.map(|p| longest[p].0).unwrap_or(0);
+            longest.insert(node, (start + duration(node), previous));
+        }
+
+        // Follow chain back from the last node to finish
+        let mut node = match longest.iter().max_by_key(|(n, (finish, _))| (*finish, Reverse(*n))) {
+            Some((node, _)) => Some(*node),
+            None => return Ok((Vec::new(), 0)),
+        };
+        let total = longest[node.unwrap()].0;
+        let mut path = Vec::new();
+        while let Some(n) = node {
+            path.push(n.clone());
+            node = longest[n].1;
+        }
+        path.reverse();
+
+        Ok((path, total))
+    }
+
+    // Scanning ///////////////////////////////////////////
+
+    /*
+     Find the next node without any remaining dependencies.
+     Ties are broken by the lowest priority key, then by the node itself.
+    */
+    fn find_next<K, F>(remaining: &BTreeMap<N, Vec<N>>, priority: &F) -> Option<N>
+        where K: Ord,
+              F: Fn(&N) -> K
+    {
+        remaining.iter()
+            .filter(|(_, dependencies)| dependencies.is_empty())
+            .min_by_key(|(node, _)| (priority(node), *node))
+            .map(|(node, _)| node.clone())
+    }
+
+    /*
+     Remove a node as a listed dependency from all remaining nodes.
+
+     This can be used to signify that a node is complete,
+     and therefore other nodes may now be available.
+    */
+    fn remove_dependency(node: &N, remaining: &mut BTreeMap<N, Vec<N>>) {
+        for dependencies in remaining.values_mut() {
+            dependencies.retain(|d| d != node)
+        }
+    }
+
+    /*
+     Same as order_by(), except every remaining node is scanned to find the next available node,
+     and every remaining dependency list is scanned to remove each completed node.
+     Kept for comparison, as this takes O(V·(V+E)) time rather than O((V+E)·log(V)).
+    */
+    #[allow(dead_code)]
+    pub fn order_by_scan<K, F>(&self, priority: F) -> Result<Vec<N>, InvalidDag<N>>
+        where K: Ord,
+              F: Fn(&N) -> K
+    {
+        self.validate()?;
+
+        let mut remaining = self.dependencies.clone();
+        let mut order = Vec::new();
+        while let Some(node) = Dag::find_next(&remaining, &priority) {
+
+            // Mark node as complete
+            remaining.remove(&node);
+            Dag::remove_dependency(&node, &mut remaining);
+            order.push(node);
+        }
+        Ok(order)
+    }
+
+    /*
+     Same as simulate(), except available nodes are found by scanning as in order_by_scan().
+    */
+    #[allow(dead_code)]
+    pub fn simulate_scan<K, D, F>(&self, workers: usize, duration: D, priority: F) -> Result<usize, InvalidDag<N>>
+        where K: Ord,
+              D: Fn(&N) -> usize,
+              F: Fn(&N) -> K
+    {
+        self.validate()?;
+
+        let mut time = 0;
+        let mut idle = workers.max(1);
+
+        // Nodes are either waiting to be worked on, being worked on, or complete
+        let mut remaining = self.dependencies.clone();
+        let mut working = BinaryHeap::new();
+
+        // Schedule work until all nodes are complete
+        while !remaining.is_empty() || !working.is_empty() {
+
+            // Assign nodes to idle workers
+            while idle > 0 {
+                if let Some(node) = Dag::find_next(&remaining, &priority) {
+                    // Schedule node completion
+                    let complete_at = time + duration(&node);
+                    remaining.remove(&node);
+                    working.push(Reverse((complete_at, node)));
+                    idle -= 1;
+                } else {
+                    // No more work available right now
+                    break
+                }
+            }
+
+            // Jump to time of next node completion
+            let Reverse((complete_at, node)) = match working.pop() {
+                Some(next) => next,
+                None => break,
+            };
+            Dag::remove_dependency(&node, &mut remaining);
+            time = complete_at;
+            idle += 1;
+        }
+
+        Ok(time)
+    }
+}
+
+impl<N: Ord + Clone + FromStr> Dag<N> {
+
+    /*
+     Parse a graph from a list of instructions in the following format:
+         Step C must be finished before step A can begin.
+    */
+    pub fn parse(instructions: &[String]) -> Result<Dag<N>, String> {
+        let mut dag = Dag::new();
+
+        for instruction in instructions {
+            let invalid = || format!("Invalid instruction: {}", instruction);
+            let words = instruction.split_whitespace().collect::<Vec<&str>>();
+
+            if let ["Step", dependency, "must", "be", "finished", "before", "step", node, "can", "begin."] = words[..] {
+                let dependency = dependency.parse::<N>().map_err(|_| invalid())?;
+                let node = node.parse::<N>().map_err(|_| invalid())?;
+                dag.add_dependency(node, dependency);
+            } else {
+                return Err(invalid())
+            }
+        }
+
+        Ok(dag)
+    }
+}
+
+impl<N: Ord + Clone + Display> Dag<N> {
+
+    /*
+     Format the graph in Graphviz's DOT language, with an edge from each dependency to the
+     nodes depending on it, and each node labelled with its duration. e.g.
+         digraph {
+             rankdir=LR;
+             node [shape=box, style=filled, fillcolor=white];
+             "A" [label="A\n1"];
+             "C" [label="C\n3"];
+             "C" -> "A";
+         }
+
+     If a schedule is given, nodes are coloured by the worker which completed them and labelled
+     with when. If a path is given (e.g. the critical path), its nodes and edges are highlighted.
+    */
+    #[allow(dead_code)]
+    pub fn to_dot<D>(&self, duration: D, schedule: Option<&Schedule<N>>, path: &[N]) -> String
+        where D: Fn(&N) -> usize
+    {
+        const WORKER_COLOURS: [&str; 8] = [
+            "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3", "#fdb462", "#b3de69", "#fccde5"
+        ];
+        let escape = |node: &N| node.to_string().replace('\\', "\\\\").replace('"', "\\\"");
+        let quote = |node: &N| format!("\"{}\"", escape(node));
+        let tasks = schedule.iter()
+            .flat_map(|s| s.tasks.iter())
+            .map(|t| (&t.node, t))
+            .collect::<BTreeMap<&N, &Task<N>>>();
+
+        let mut out = String::from("digraph {\n    rankdir=LR;\n");
+        out += "    node [shape=box, style=filled, fillcolor=white];\n";
+
+        for node in self.nodes() {
+            let mut label = format!("{}\\n{}", escape(node), duration(node));
+            let mut attributes = Vec::new();
+            if let Some(task) = tasks.get(node) {
+                label += &format!("\\nWorker {} ({}-{})", task.worker + 1, task.start, task.end);
+                attributes.push(format!("fillcolor=\"{}\"", WORKER_COLOURS[task.worker % WORKER_COLOURS.len()]));
+            }
+            if path.contains(node) {
+                attributes.push(String::from("color=red, penwidth=3"));
+            }
+            attributes.insert(0, format!("label=\"{}\"", label));
+            out += &format!("    {} [{}];\n", quote(node), attributes.join(", "));
+        }
+
+        for (node, dependencies) in &self.dependencies {
+            for dependency in dependencies {
+                let critical = path.windows(2).any(|w| w[0] == *dependency && w[1] == *node);
+                let style = if critical { " [color=red, penwidth=3]" } else { "" };
+                out += &format!("    {} -> {}{};\n", quote(dependency), quote(node), style);
+            }
+        }
+
+        out += "}\n";
+        out
+    }
+}
+
+// Workers ////////////////////////////////////////////////////////////////////
+
+/*
+ Someone who works on nodes, at a speed relative to a normal worker as a percentage
+ (e.g. 200 takes half as long), and who may only be able to work on some nodes.
+*/
+#[derive(Clone, Debug)]
+pub struct Worker<N> {
+    pub speed: usize,
+    pub skills: Option<BTreeSet<N>>, // Nodes able to be worked on, or None for any
+}
+
+impl<N: Ord> Worker<N> {
+
+    /*
+     Create a worker of normal speed, able to work on any node.
+    */
+    pub fn new() -> Worker<N> {
+        Worker { speed: 100, skills: None }
+    }
+
+    #[allow(dead_code)]
+    pub fn with_speed(mut self, speed: usize) -> Worker<N> {
+        self.speed = speed;
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_skills<I: IntoIterator<Item=N>>(mut self, nodes: I) -> Worker<N> {
+        self.skills = Some(nodes.into_iter().collect());
+        self
+    }
+
+    pub fn can_work_on(&self, node: &N) -> bool {
+        self.skills.as_ref().map(|s| s.contains(node)).unwrap_or(true)
+    }
+
+    /*
+     Find the time taken for this worker to complete a node which takes a normal worker the
+     given duration, rounded up to a whole unit of time.
+    */
+    pub fn time_for(&self, duration: usize) -> usize {
+        let speed = self.speed.max(1);
+        (duration * 100).div_ceil(speed)
+    }
+}
+
+/*
+ A policy for choosing which available node a free worker takes next.
+ Ties are broken by the nodes' ordering.
+*/
+#[allow(dead_code)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Dispatch {
+    Alphabetical,      // The first available node
+    LongestFirst,      // The node which takes longest
+    CriticalPathFirst, // The node with the longest chain of dependent nodes after it (including itself)
+}
+
+// Schedule ///////////////////////////////////////////////////////////////////
+
+/*
+ A node worked on by a worker from the start time until (but not including) the end time.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct Task<N> {
+    pub node: N,
+    pub worker: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/*
+ When and by which worker every node in a graph is completed.
+*/
+pub struct Schedule<N> {
+    pub workers: usize,
+    pub tasks: Vec<Task<N>>, // In order of starting
+}
+
+impl<N> Schedule<N> {
+
+    /*
+     Get the total time taken to complete every node.
+    */
+    pub fn duration(&self) -> usize {
+        self.tasks.iter().map(|t| t.end).max().unwrap_or(0)
+    }
+
+    /*
+     Get the total time spent working on nodes by all workers.
+    */
+    #[allow(dead_code)]
+    pub fn work(&self) -> usize {
+        self.tasks.iter().map(|t| t.end - t.start).sum()
+    }
+
+    /*
+     Get the tasks performed by a worker, in order.
+    */
+    #[allow(dead_code)]
+    pub fn worker_tasks(&self, worker: usize) -> Vec<&Task<N>> {
+        self.tasks.iter().filter(|t| t.worker == worker).collect()
+    }
+
+    /*
+     Get the periods (from start until end) when a worker isn't working before every node is complete.
+    */
+    #[allow(dead_code)]
+    pub fn idle_periods(&self, worker: usize) -> Vec<(usize, usize)> {
+        let mut periods = Vec::new();
+        let mut time = 0;
+        for task in self.worker_tasks(worker) {
+            if task.start > time { periods.push((time, task.start)) }
+            time = task.end;
+        }
+        if self.duration() > time { periods.push((time, self.duration())) }
+        periods
+    }
+
+    /*
+     Find the task being worked on by a worker at a point in time, if any.
+    */
+    fn task_at(&self, worker: usize, time: usize) -> Option<&Task<N>> {
+        self.tasks.iter().find(|t| t.worker == worker && t.start <= time && time < t.end)
+    }
+}
+
+impl<N: Display> Schedule<N> {
+
+    /*
+     Format the schedule as a table of what each worker is doing every second, and which
+     nodes are done, as in the puzzle description. e.g.
+         Second Worker 1   Worker 2   Done
+            0       C          .
+            1       C          .
+            2       C          .
+            3       A          F       C
+    */
+    #[allow(dead_code)]
+    pub fn to_table(&self) -> String {
+        let mut out = String::from("Second");
+        for worker in 0..self.workers {
+            out += &format!("{:^11}", format!("Worker {}", worker + 1));
+        }
+        out += " Done\n";
+
+        let mut done = self.tasks.iter().collect::<Vec<&Task<N>>>();
+        done.sort_by_key(|t| t.end);
+        for time in 0..=self.duration() {
+            let mut row = format!("{:>4}  ", time);
+            for worker in 0..self.workers {
+                let node = self.task_at(worker, time).map(|t| t.node.to_string());
+                row += &format!("{:^11}", node.unwrap_or(String::from(".")));
+            }
+            row += " ";
+            row.extend(done.iter().filter(|t| t.end <= time).map(|t| t.node.to_string()));
+            out += row.trim_end();
+            out += "\n";
+        }
+        out
+    }
+
+    /*
+     Format the schedule as a Gantt chart with one row per worker, scaled to fit within
+     max_columns. Each task is drawn as its node followed by dashes, and idle time as dots.
+     e.g.
+         Worker 1 |C--A-B-D----E-----.
+         Worker 2 |...F------........
+    */
+    #[allow(dead_code)]
+    pub fn to_gantt(&self, max_columns: usize) -> String {
+        let duration = self.duration();
+        let scale = duration.div_ceil(max_columns.max(1)).max(1);
+        let columns = duration.div_ceil(scale);
+
+        let mut out = String::new();
+        for worker in 0..self.workers {
+            out += &format!("Worker {:<2}|", worker + 1);
+            let mut current: Option<(&Task<N>, Vec<char>)> = None; // Task and remaining label
+            for column in 0..columns {
+                let task = self.task_at(worker, column * scale);
+                match (task, &mut current) {
+                    (Some(task), Some((current_task, label))) if std::ptr::eq(*current_task, task) => {
+                        out.push(if label.is_empty() { '-' } else { label.remove(0) });
+                    },
+                    (Some(task), _) => {
+                        let mut label = task.node.to_string().chars().collect::<Vec<char>>();
+                        out.push(label.remove(0));
+                        current = Some((task, label));
+                    },
+                    (None, _) => {
+                        out.push('.');
+                        current = None;
+                    }
+                }
+            }
+            out += "\n";
+        }
+        out += &format!("Scale: 1 column = {} second(s), total {} seconds\n", scale, duration);
+        out
+    }
+}
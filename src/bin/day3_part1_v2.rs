@@ -0,0 +1,35 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod fabric;
+use self::fabric::*;
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find the total area of overlapping claims.
+ Each claim is represented by a single line defining a rectangle of any size and position.
+*/
+fn solve(lines: &[String]) -> i64 {
+    let claims = lines.iter()
+        .map(|l| Claim::parse(l))
+        .collect::<Vec<Claim>>();
+
+    find_overlap_area(&claims)
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+run! {
+    input = "day3",
+    run = |input: &Input| {
+        let overlap = solve(&input.to_lines());
+        assert_eq!(overlap, 121259);
+        println!("Overlap: {}", overlap);
+    },
+    bench = |input: &Input| {
+        // DEBUG: ~16.7ms
+        // RELEASE: ~2.84ms
+        solve(&input.to_lines())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,123 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod fabric;
+use self::fabric::*;
+
+mod image;
+use self::image::*;
+
+// Settings ///////////////////////////////////////////////////////////////////
+
+const HEATMAP_SIZE: u64 = 1000; // Largest width or height of the heatmap in pixels
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find the id of the non-overlapping claim.
+ Each claim is represented by a single line defining a rectangle of any size and position.
+*/
+fn solve(lines: &[String]) -> i32 {
+    let claims = lines.iter()
+        .map(|l| Claim::parse(l))
+        .collect::<Vec<Claim>>();
+
+    let clean = find_non_overlapping(&claims);
+    assert_eq!(clean.len(), 1, "Expected exactly one non-overlapping claim");
+
+    clean[0].id
+}
+
+/*
+ Render a heatmap of the number of claims covering each square inch.
+
+ Unclaimed fabric is dark, fabric claimed once is blue, and overlapping claims
+ range from yellow to red as the number of claims increases.
+ The highlighted claim is filled green, and the outlined claim (if any) is outlined white.
+ Fabric too large for the heatmap is scaled down, with each pixel showing the square inch
+ at its top left.
+*/
+fn render_heatmap(lines: &[String], highlight: i32, outline: Option<i32>) -> Result<Image, String> {
+    let claims = lines.iter()
+        .map(|l| Claim::parse(l))
+        .collect::<Vec<Claim>>();
+    let sheet = FabricSheet::from_claims(&claims)?;
+    let max_count = sheet.max_count().max(3);
+
+    // Fit fabric to the heatmap
+    let (left, top, right, bottom) = sheet.bounds().ok_or("No claims to render")?;
+    let too_far = || format!("Claims are too far apart to render: {}..{} x {}..{}", left, right, top, bottom);
+    let width = right.checked_sub(left).ok_or_else(too_far)? as u64;
+    let height = bottom.checked_sub(top).ok_or_else(too_far)? as u64;
+    let scale = width.max(height).div_ceil(HEATMAP_SIZE).max(1);
+    let to_image = |x: i64, y: i64| (((x - left) as u64 / scale) as usize, ((y - top) as u64 / scale) as usize);
+    let to_fabric = |i: usize, j: usize| (left + (i as u64 * scale) as i64, top + (j as u64 * scale) as i64);
+
+    // Colour by claim density
+    let mut image = Image::new(width.div_ceil(scale) as usize, height.div_ceil(scale) as usize, Colour::new(16, 16, 32));
+    for j in 0..image.height() {
+        for i in 0..image.width() {
+            let (x, y) = to_fabric(i, j);
+            let colour = match sheet.count_at(x, y) {
+                0 => continue,
+                1 => Colour::new(40, 70, 140),
+                n => {
+                    let heat = (n - 2) as f64 / (max_count - 2) as f64;
+                    Colour::new(255, 220, 0).lerp(Colour::new(220, 0, 0), heat)
+                }
+            };
+            image.set(i, j, colour);
+        }
+    }
+
+    // Mark claims of interest
+    let corners = |c: &Claim| (to_image(c.x, c.y), to_image(c.right() - 1, c.bottom() - 1));
+    for claim in claims.iter().filter(|c| c.id == highlight) {
+        let ((from_i, from_j), (to_i, to_j)) = corners(claim);
+        for j in from_j..=to_j {
+            for i in from_i..=to_i {
+                image.set(i, j, Colour::new(0, 200, 0));
+            }
+        }
+    }
+    for claim in claims.iter().filter(|c| Some(c.id) == outline) {
+        let ((from_i, from_j), (to_i, to_j)) = corners(claim);
+        image.outline(from_i, from_j, to_i - from_i + 1, to_j - from_j + 1, Colour::WHITE);
+    }
+
+    Ok(image)
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+run! {
+    input = "day3",
+    options = [
+        "heatmap", // Path to export the heatmap to as a PPM image, e.g. `cargo run --bin day3_part2_v2 -- --heatmap heatmap.ppm`
+        "outline", // Claim ID to outline on the heatmap
+        "ansi",    // Columns to print the heatmap in, using true-colour ANSI escape codes
+    ],
+    run = |input: &Input| {
+        let id = solve(&input.to_lines());
+        assert_eq!(id, 239);
+        println!("Non-overlapping ID: {}", id);
+
+        // Export heatmap of claim density
+        let heatmap = render_heatmap(&input.to_lines(), id, input.parse_option("outline"))
+            .unwrap_or_else(|e| panic!("{}", e));
+        if let Some(path) = input.option("heatmap") {
+            heatmap.write_ppm(path).unwrap();
+            println!("Heatmap written to: {}", path);
+        }
+
+        // Print heatmap to stdout
+        if let Some(columns) = input.parse_option("ansi") {
+            print!("{}", heatmap.to_ansi(columns));
+        }
+    },
+    bench = |input: &Input| {
+        // DEBUG: ~36.0ms
+        // RELEASE: ~4.04ms
+        solve(&input.to_lines())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,73 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod fabric;
+use self::fabric::*;
+
+mod claim_index;
+use self::claim_index::*;
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find the id of the non-overlapping claim, along with the groups of claims connected by overlap.
+ Each claim is represented by a single line defining a rectangle of any size and position.
+*/
+fn solve(lines: &[String]) -> (i32, Vec<Vec<i32>>) {
+    let claims = lines.iter()
+        .map(|l| Claim::parse(l))
+        .collect::<Vec<Claim>>();
+    let index = ClaimIndex::new(&claims);
+
+    // Find the claim which doesn't overlap
+    let clean = claims.iter()
+        .filter(|c| index.overlapping(c.id).unwrap().is_empty())
+        .collect::<Vec<&Claim>>();
+    assert_eq!(clean.len(), 1, "Expected exactly one non-overlapping claim");
+
+    (clean[0].id, index.overlap_groups())
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+run! {
+    input = "day3",
+    run = |input: &Input| {
+        let (id, groups) = solve(&input.to_lines());
+        assert_eq!(id, 239);
+        assert_eq!(groups.iter().filter(|g| g.len() == 1).count(), 1);
+        println!("Non-overlapping ID: {}", id);
+        println!("Overlap groups: {} (largest has {} claims)", groups.len(), groups[0].len());
+
+        // Check queries against scanning every claim: overlaps of each claim, and point queries
+        // at the corners of each claim and just beyond them
+        let claims = input.to_lines().iter().map(|l| Claim::parse(l)).collect::<Vec<Claim>>();
+        let index = ClaimIndex::new(&claims);
+        let ids = |found: Vec<&Claim>| found.iter().map(|c| c.id).collect::<Vec<i32>>();
+        for claim in &claims {
+            let overlapping = index.overlapping(claim.id).unwrap().iter()
+                .map(|(other, area)| (other.id, *area))
+                .collect::<Vec<(i32, i64)>>();
+            let mut scanned = claims.iter()
+                .filter(|other| other.id != claim.id && claim.overlap_area(other) > 0)
+                .map(|other| (other.id, claim.overlap_area(other)))
+                .collect::<Vec<(i32, i64)>>();
+            scanned.sort();
+            assert_eq!(overlapping, scanned);
+
+            for &(x, y) in &[(claim.x, claim.y), (claim.right() - 1, claim.bottom() - 1), (claim.right(), claim.bottom())] {
+                let mut scanned = ids(claims.iter().filter(|c| c.contains(x, y)).collect());
+                scanned.sort();
+                assert_eq!(ids(index.covering(x, y)), scanned);
+            }
+        }
+        let (x, y) = (claims[0].x, claims[0].y);
+        let covering = ids(index.covering(x, y));
+        println!("Claims covering ({},{}): {:?}", x, y, covering);
+    },
+    bench = |input: &Input| {
+        // DEBUG: ~27.3ms
+        // RELEASE: ~4.36ms
+        solve(&input.to_lines())
+    }
+}
\ No newline at end of file
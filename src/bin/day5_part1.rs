@@ -4,22 +4,23 @@ use self::common::*;
 mod sparse_vector;
 use self::sparse_vector::*;
 
-// Functions //////////////////////////////////////////////////////////////////
+mod polymer;
+use self::polymer::*;
 
-/*
- Check if two chars are the same letter with opposing capitalization.
-*/
-fn can_react(a: char, b: char) -> bool {
-    (a as i32 - b as i32).abs() == 32
-}
+// Settings /////////////////////////////////////////////////////////////////////
+
+const TRACE_PATH: &str = "res/other/day5_part1_trace.txt";
+
+// Functions //////////////////////////////////////////////////////////////////
 
 /*
  Process polymer reactions until inert.
+ Reactions which create a product replace the left unit, and remove the right.
 */
-fn react(polymer: &String) -> String {
+fn react<R: ReactionRule<char>>(polymer: &str, rule: &R) -> String {
 
     // Create sparse vector to represent polymer
-    let polymer= polymer.clone()
+    let polymer= polymer
         .chars()
         .collect::<Vec<char>>();
     let mut sparse_vec = SparseVector::from_vec(polymer);
@@ -33,12 +34,21 @@ fn react(polymer: &String) -> String {
         }
         let right = *cursor.get();
 
-        if can_react(left, right) {
-            cursor.remove_then_prev(); // Remove right
-            cursor.remove_then_prev(); // Remove left
-            // Next iteration will compare either side of gap
-        } else {
-            // Next iteration will compare next pair
+        match rule.react(left, right) {
+            Reaction::Annihilate => {
+                cursor.remove_then_prev(); // Remove right
+                cursor.remove_then_prev(); // Remove left
+                // Next iteration will compare either side of gap
+            },
+            Reaction::Product(product) => {
+                cursor.remove_then_prev(); // Remove right
+                *cursor.get_mut() = product; // Replace left
+                cursor.move_prev();
+                // Next iteration will compare product with its left (if any)
+            },
+            Reaction::Inert => {
+                // Next iteration will compare next pair
+            }
         }
     }
 
@@ -49,7 +59,7 @@ fn react(polymer: &String) -> String {
  Find length of polymer after all interactions have been resolved.
 */
 fn solve(polymer: &String) -> usize {
-    react(polymer).len()
+    react(polymer, &OppositePolarity).len()
 }
 
 // Entry Point ////////////////////////////////////////////////////////////////
@@ -65,6 +75,27 @@ run!{
         let units_remaining = solve(input.raw());
         assert_eq!(units_remaining, 11814);
         println!("Units Remaining: {}", units_remaining);
+
+        // Check other reaction rules: an explicit set of pairs which annihilate in either order,
+        // and a table of pairs which annihilate or create a product
+        let pairs = AnnihilationPairs::new(&[('x', 'y'), ('p', 'q')]);
+        assert_eq!(react("yqpxz", &pairs), "z");
+        let table = RewriteTable::new()
+            .with('a', 'b', Reaction::Product('c'))
+            .with('c', 'c', Reaction::Annihilate)
+            .with('c', 'a', Reaction::Product('b'));
+        assert_eq!(react("xabcabab", &table), "xbb");
+        assert_eq!(polymer::react("xabcabab".chars(), &table).into_iter().collect::<String>(), "xbb");
+
+        // Export reaction trace
+        let polymer = input.raw().trim().as_bytes();
+        let trace = react_traced(polymer.iter().cloned().enumerate(), &OppositePolarity);
+        assert_eq!(trace.survivors.len(), units_remaining);
+        println!("Reactions: {} over {} passes", trace.reactions.len(), trace.passes());
+        let export = format!("{}\n{}\n", trace.to_list(polymer), trace.to_visualisation());
+        std::fs::write(TRACE_PATH, export)
+            .unwrap_or_else(|e| panic!("Failed to write trace: {}", e));
+        println!("Trace written to {}", TRACE_PATH);
     },
     bench = |input: &Input| {
         solve(input.raw());
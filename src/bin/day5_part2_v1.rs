@@ -4,24 +4,21 @@ use self::common::*;
 mod sparse_vector;
 use self::sparse_vector::*;
 
+mod polymer;
+use self::polymer::*;
+
 use itertools::Itertools;
 
 // Functions //////////////////////////////////////////////////////////////////
 
-/*
- Check if two chars are the same letter with opposing capitalization.
-*/
-fn can_react(a: char, b: char) -> bool {
-    (a as i32 - b as i32).abs() == 32
-}
-
 /*
  Process polymer reactions until inert.
+ Reactions which create a product replace the left unit, and remove the right.
 */
-fn react(polymer: &String) -> String {
+fn react<R: ReactionRule<char>>(polymer: &str, rule: &R) -> String {
 
     // Create sparse vector to represent polymer
-    let polymer= polymer.clone()
+    let polymer= polymer
         .chars()
         .collect::<Vec<char>>();
     let mut sparse_vec = SparseVector::from_vec(polymer);
@@ -35,12 +32,21 @@ fn react(polymer: &String) -> String {
         }
         let right = *cursor.get();
 
-        if can_react(left, right) {
-            cursor.remove_then_prev(); // Remove right
-            cursor.remove_then_prev(); // Remove left
-            // Next iteration will compare either side of gap
-        } else {
-            // Next iteration will compare next pair
+        match rule.react(left, right) {
+            Reaction::Annihilate => {
+                cursor.remove_then_prev(); // Remove right
+                cursor.remove_then_prev(); // Remove left
+                // Next iteration will compare either side of gap
+            },
+            Reaction::Product(product) => {
+                cursor.remove_then_prev(); // Remove right
+                *cursor.get_mut() = product; // Replace left
+                cursor.move_prev();
+                // Next iteration will compare product with its left (if any)
+            },
+            Reaction::Inert => {
+                // Next iteration will compare next pair
+            }
         }
     }
 
@@ -72,7 +78,7 @@ fn solve(polymer: &String) -> (usize, char) {
     // React polymers without each unit type
     let polymers = unit_types.iter()
         .map(|c| remove_unit_type(polymer, *c))
-        .map(|p| react(&p));
+        .map(|p| react(&p, &OppositePolarity));
 
     // Find shortest polymer
     let (len,unit) = polymers
@@ -101,6 +107,17 @@ run!{
 
         println!("Bad unit: {}", bad_unit);
         println!("Shortest length: {}", shortest_length);
+
+        // Check other reaction rules: an explicit set of pairs which annihilate in either order,
+        // and a table of pairs which annihilate or create a product
+        let pairs = AnnihilationPairs::new(&[('x', 'y'), ('p', 'q')]);
+        assert_eq!(react("yqpxz", &pairs), "z");
+        let table = RewriteTable::new()
+            .with('a', 'b', Reaction::Product('c'))
+            .with('c', 'c', Reaction::Annihilate)
+            .with('c', 'a', Reaction::Product('b'));
+        assert_eq!(react("xabcabab", &table), "xbb");
+        assert_eq!(polymer::react("xabcabab".chars(), &table).into_iter().collect::<String>(), "xbb");
     },
     bench = |input: &Input| {
         solve(input.raw());
@@ -1,51 +1,13 @@
 #[macro_use] mod common;
 use self::common::*;
 
+mod polymer;
+use self::polymer::*;
+
 use itertools::Itertools;
 
 // Functions //////////////////////////////////////////////////////////////////
 
-/*
- Check if two chars are the same letter with opposing capitalization.
-*/
-fn can_react(a: char, b: char) -> bool {
-    (a as i32 - b as i32).abs() == 32
-}
-
-/*
- Process polymer reactions until inert.
-
- Stack-based solution for efficient removal adapted from:
- https://www.reddit.com/r/adventofcode/comments/a3912m/2018_day_5_solutions/
-
- When when two units react together, one will be in the current iteration, while
- the other will be on the top of the stack. This allows us to efficiently discard them
- with minimal operations compared to a vector (requires re-shuffling; O(n) removals) or
- a double-linked list / sparse array (O(1) removals, but with additional overhead).
-*/
-fn react(polymer: &String) -> String {
-
-    let mut stack = Vec::with_capacity(polymer.len());
-    stack.push('+'); // Unmatchable element to simplify logic
-
-    // Process unit reactions in a single pass
-    for right in polymer.chars() {
-        let left = *stack.last().unwrap();
-        if can_react(left, right) {
-            // Discard both units
-            stack.pop(); // Discard left unit
-            // Right unit will remain unused (and be discarded)
-        } else {
-            // Keep both units (at least for now)
-            stack.push(right) // Keep right unit
-            // Left unit is kept on the stack
-        }
-    }
-
-    // Get reacted polymer (excluding first temp)
-    stack.iter().skip(1).collect::<String>()
-}
-
 /*
  Remove all units of the specified type from a polymer.
 */
@@ -69,12 +31,14 @@ fn solve(polymer: &String) -> (usize, char) {
         .collect::<Vec<char>>();
 
     // Perform initial reaction
-    let polymer = react(polymer);
+    let polymer = react(polymer.chars(), &OppositePolarity)
+        .into_iter()
+        .collect::<String>();
 
     // React polymers without each unit type
     let polymers = unit_types.iter()
         .map(|c| remove_unit_type(&polymer, *c))
-        .map(|p| react(&p));
+        .map(|p| react(p.chars(), &OppositePolarity));
 
     // Find shortest polymer
     let (len,unit) = polymers
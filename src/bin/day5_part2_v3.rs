@@ -0,0 +1,48 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod polymer;
+use self::polymer::*;
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find length of the shortest polymer after removing one unit type and resolving all interactions.
+ The polymer is streamed from the reader, and never held in memory in its entirety.
+*/
+fn solve<R: std::io::Read>(polymer: R) -> (usize, char) {
+    let unit_types = (b'a'..=b'z').collect::<Vec<u8>>();
+    let lengths = react_stream(polymer, &OppositePolarity, &unit_types).unwrap();
+
+    // Find shortest polymer
+    let (unit, len) = lengths.without.iter()
+        .min_by(|(_, l1), (_, l2)| l1.cmp(l2))
+        .unwrap();
+
+    (*len, *unit as char)
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ Timings:
+    DEBUG: ~40.2ms
+    RELEASE: ~3.74ms
+*/
+run!{
+    input = "day5",
+    run = |input: &Input| {
+        let file = std::fs::File::open(input.path()).unwrap();
+        let (shortest_length, bad_unit) = solve(file);
+
+        assert_eq!(bad_unit, 'g');
+        assert_eq!(shortest_length, 4282);
+
+        println!("Bad unit: {}", bad_unit);
+        println!("Shortest length: {}", shortest_length);
+    },
+    bench = |input: &Input| {
+        let file = std::fs::File::open(input.path()).unwrap();
+        solve(file);
+    }
+}
\ No newline at end of file
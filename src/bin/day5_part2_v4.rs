@@ -0,0 +1,72 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod polymer;
+use self::polymer::*;
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find length of the shortest polymer after removing one unit type and resolving all interactions.
+ Polymers are reacted in place as bytes, using the reacted prefix of each polymer as the stack.
+*/
+fn solve(polymer: &str) -> (usize, char) {
+
+    // Perform initial reaction
+    let mut polymer = polymer.trim().as_bytes().to_vec();
+    let len = react_in_place(&mut polymer, &OppositePolarity);
+    polymer.truncate(len);
+
+    // React polymers without each unit type
+    let mut buffer = Vec::with_capacity(polymer.len());
+    let (unit, len) = (b'a'..=b'z')
+        .map(|unit| {
+            buffer.clear();
+            buffer.extend(polymer.iter().filter(|u| u.to_ascii_lowercase() != unit));
+            (unit, react_in_place(&mut buffer, &OppositePolarity))
+        })
+        .min_by(|(_, l1), (_, l2)| l1.cmp(l2))
+        .unwrap();
+
+    (len, unit as char)
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ Timings:
+    DEBUG: ~17.8ms
+    RELEASE: ~2.20ms
+*/
+run!{
+    input = "day5",
+    run = |input: &Input| {
+        let (shortest_length, bad_unit) = solve(input.raw());
+
+        assert_eq!(bad_unit, 'g');
+        assert_eq!(shortest_length, 4282);
+
+        println!("Bad unit: {}", bad_unit);
+        println!("Shortest length: {}", shortest_length);
+
+        // Explain why the bad unit shortens the polymer the most
+        let polymer = input.raw().trim().as_bytes();
+        let mut explanations = (b'a'..=b'z')
+            .map(|unit| explain_removal(polymer, unit, &OppositePolarity))
+            .collect::<Vec<RemovalExplanation>>();
+        explanations.sort_by_key(|e| std::cmp::Reverse(e.shortened_by()));
+        let (best, runner_up) = (&explanations[0], &explanations[1]);
+        assert_eq!(best.unit_type as char, bad_unit);
+
+        println!("Removing '{}' removes {} units and unlocks {} reactions in {} chains, shortening by {} (next best '{}' by {})",
+                 bad_unit, best.removed, best.unlocked, best.chains.len(), best.shortened_by(),
+                 runner_up.unit_type as char, runner_up.shortened_by());
+        for chain in best.chains.iter().take(5) {
+            println!("  {:>5} units ({} removed, {} reactions): {}", chain.units_eliminated(),
+                     chain.removed.len(), chain.reactions.len(), chain.to_string(polymer));
+        }
+    },
+    bench = |input: &Input| {
+        solve(input.raw());
+    }
+}
\ No newline at end of file
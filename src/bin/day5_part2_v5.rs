@@ -0,0 +1,59 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod polymer;
+use self::polymer::*;
+
+// Settings ///////////////////////////////////////////////////////////////////
+
+const THREADS: usize = 4;
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find length of the shortest polymer after removing one unit type and resolving all interactions.
+ Polymers are split into one chunk per thread and reacted in place as bytes, then merged.
+*/
+fn solve(polymer: &str, threads: usize) -> (usize, char) {
+
+    // Perform initial reaction
+    let mut polymer = polymer.trim().as_bytes().to_vec();
+    let len = react_in_place_parallel(&mut polymer, &OppositePolarity, threads);
+    polymer.truncate(len);
+
+    // React polymers without each unit type
+    let mut buffer = Vec::with_capacity(polymer.len());
+    let (unit, len) = (b'a'..=b'z')
+        .map(|unit| {
+            buffer.clear();
+            buffer.extend(polymer.iter().filter(|u| u.to_ascii_lowercase() != unit));
+            (unit, react_in_place_parallel(&mut buffer, &OppositePolarity, threads))
+        })
+        .min_by(|(_, l1), (_, l2)| l1.cmp(l2))
+        .unwrap();
+
+    (len, unit as char)
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ Timings:
+    DEBUG: ~18.0ms
+    RELEASE: ~4.46ms (4 threads)
+*/
+run!{
+    input = "day5",
+    run = |input: &Input| {
+        let (shortest_length, bad_unit) = solve(input.raw(), THREADS);
+
+        assert_eq!(bad_unit, 'g');
+        assert_eq!(shortest_length, 4282);
+
+        println!("Bad unit: {}", bad_unit);
+        println!("Shortest length: {}", shortest_length);
+    },
+    bench = |input: &Input| {
+        solve(input.raw(), THREADS);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,67 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod geometry;
+use self::geometry::*;
+
+mod image;
+
+mod territory_map;
+use self::territory_map::*;
+
+// Settings ///////////////////////////////////////////////////////////////////
+
+const MAP_PATH: &str = "res/other/day6_part2_safe_region.ppm";
+const MAP_MARGIN: i32 = 50; // Coordinates to render beyond the points on each side
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find the area of the safe region.
+ i.e. points with a combined distance of < 10,000 from all other points.
+*/
+fn solve(points: Vec<String>) -> usize {
+
+    // Parse points
+    let points = points.iter()
+        .map(|p| Point::parse(p))
+        .collect::<Result<Vec<Point>, String>>()
+        .unwrap_or_else(|e| panic!("{}", e));
+
+    manhattan_region_size(&points, 10000 - 1)
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ Timings:
+    DEBUG: ~247us
+    RELEASE: ~24.4us
+*/
+run! {
+    input = "day6",
+    run = |input: &Input| {
+        let safe_region_size = solve(input.to_lines());
+        assert_eq!(safe_region_size, 39560);
+        println!("Area of safe region: {}", safe_region_size);
+
+        // Compare other metrics
+        let points = input.to_lines().iter()
+            .map(|p| Point::parse(p).unwrap())
+            .collect::<Vec<Point>>();
+        assert_eq!(region_size(&points, &Manhattan, 10000 - 1), safe_region_size);
+
+        // Export map of territories with safe region outlined
+        let bound = Rect::bounding(&points).unwrap().expand(MAP_MARGIN);
+        let mut map = TerritoryMap::new(&points, &manhattan_voronoi(&points), &Manhattan, bound);
+        map.outline_region(&points, &Manhattan, 10000 - 1);
+        map.image().write_ppm(MAP_PATH).unwrap();
+        println!("Safe region map written to {}", MAP_PATH);
+        println!("Area of safe region (Chebyshev): {}", region_size(&points, &Chebyshev, 10000 - 1));
+        println!("Area of safe region (Euclidean², < 1,000,000): {}", region_size(&points, &SquaredEuclidean, 1000000 - 1));
+        println!("Area of safe region (Manhattan, y costs 2x): {}", region_size(&points, &WeightedManhattan::new(1, 2), 10000 - 1));
+    },
+    bench = |input: &Input| {
+        solve(input.to_lines());
+    }
+}
@@ -0,0 +1,110 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod dag;
+use self::dag::*;
+
+use lazy_static::lazy_static;
+
+// Settings ///////////////////////////////////////////////////////////////////
+
+const GENERATED_STEPS: usize = 3000;
+const GENERATED_MAX_DEPENDENCIES: usize = 3; // Per step
+const WORKERS: usize = 5;
+
+lazy_static! {
+    static ref GENERATED: Dag<String> = generate(GENERATED_STEPS, GENERATED_MAX_DEPENDENCIES);
+}
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Generate a random graph of steps, each depending on up to max_dependencies earlier steps.
+ Steps are named with three letters (AAA, AAB, ...) in a random order, so that the
+ alphabetical order of steps is unrelated to their dependencies.
+*/
+fn generate(steps: usize, max_dependencies: usize) -> Dag<String> {
+    let mut seed: u64 = 0x2545F4914F6CDD1D;
+    let mut random = |below: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % below as u64) as usize
+    };
+
+    // Shuffle names
+    let mut names = (0..steps)
+        .map(|i| (0..3).rev().map(|d| (b'A' + (i / 26usize.pow(d) % 26) as u8) as char).collect::<String>())
+        .collect::<Vec<String>>();
+    for i in (1..names.len()).rev() {
+        names.swap(i, random(i + 1));
+    }
+
+    // Add dependencies on earlier steps
+    let mut dag = Dag::new();
+    dag.add_node(names[0].clone());
+    for i in 1..steps {
+        dag.add_node(names[i].clone());
+        for _ in 0..random(max_dependencies + 1) {
+            let dependency = names[random(i)].clone();
+            if !dag.dependencies(&names[i]).contains(&dependency) {
+                dag.add_dependency(names[i].clone(), dependency);
+            }
+        }
+    }
+    dag
+}
+
+/*
+ Find the time taken to complete a step, where each takes 60 seconds plus the positions
+ of its letters in the alphabet (A=1, B=2, ...).
+*/
+fn duration(step: &str) -> usize {
+    60 + step.bytes().map(|b| (b - b'A') as usize + 1).sum::<usize>()
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ The "v1 rescan" functions measure v1's approach of rescanning every remaining step for the
+ next available one, and every dependency list after each completion. v1 itself only handles
+ single letter steps, so this is order_by_scan() and simulate_scan() from the dag module,
+ which port it to steps of any type.
+
+ Timings (3000 steps):
+    order (heap): DEBUG: ~7.52ms, RELEASE: ~2.76ms
+    order (v1 rescan): DEBUG: ~1.01s, RELEASE: ~115ms
+    simulate (heap): DEBUG: ~11.2ms, RELEASE: ~2.70ms
+    simulate (v1 rescan): DEBUG: ~1.03s, RELEASE: ~123ms
+*/
+run! {
+    input = "day7",
+    run = |input: &Input| {
+
+        // Check both implementations against puzzle input
+        let steps = Dag::<String>::parse(&input.to_lines()).unwrap();
+        let order = steps.order().unwrap();
+        assert_eq!(order.concat(), "HPDTNXYLOCGEQSIMABZKRUWVFJ");
+        assert_eq!(order, steps.order_by_scan(|_| ()).unwrap());
+        let alphabet_position = |s: &String| (s.as_bytes()[0] - b'A') as usize + 1;
+        assert_eq!(steps.simulate(5, |s| 60 + alphabet_position(s), |_| ()).unwrap(), 908);
+        assert_eq!(steps.simulate_scan(5, |s| 60 + alphabet_position(s), |_| ()).unwrap(), 908);
+
+        // Check both implementations against generated graph
+        let order = GENERATED.order().unwrap();
+        assert_eq!(order, GENERATED.order_by_scan(|_| ()).unwrap());
+        let time = GENERATED.simulate(WORKERS, |s| duration(s), |_| ()).unwrap();
+        assert_eq!(time, GENERATED.simulate_scan(WORKERS, |s| duration(s), |_| ()).unwrap());
+
+        let dependencies = GENERATED.nodes().map(|n| GENERATED.dependencies(n).len()).sum::<usize>();
+        println!("Generated {} steps with {} dependencies", GENERATED.len(), dependencies);
+        println!("First steps: {}", order[..10].join(", "));
+        println!("Total time to complete with {} workers: {}", WORKERS, time);
+    },
+    bench = {
+        "order (heap)" => |_: &Input| { GENERATED.order().unwrap(); },
+        "order (v1 rescan)" => |_: &Input| { GENERATED.order_by_scan(|_| ()).unwrap(); },
+        "simulate (heap)" => |_: &Input| { GENERATED.simulate(WORKERS, |s| duration(s), |_| ()).unwrap(); },
+        "simulate (v1 rescan)" => |_: &Input| { GENERATED.simulate_scan(WORKERS, |s| duration(s), |_| ()).unwrap(); },
+    }
+}
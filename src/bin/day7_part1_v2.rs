@@ -0,0 +1,60 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod dag;
+use self::dag::*;
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find the order in which steps must be completed, based on dependencies.
+ Steps which are available at the same time are completed in alphabetical order.
+*/
+fn solve(instructions: &[String]) -> String {
+    let steps = Dag::<char>::parse(instructions)
+        .unwrap_or_else(|e| panic!("{}", e));
+
+    steps.order()
+        .unwrap_or_else(|e| panic!("{}", e))
+        .into_iter()
+        .collect::<String>()
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ Timings:
+    DEBUG: ~570us
+    RELEASE: ~73.9us
+*/
+run! {
+    input = "day7",
+    run = |input: &Input| {
+        let step_order = solve(&input.to_lines());
+        assert_eq!(step_order, "HPDTNXYLOCGEQSIMABZKRUWVFJ");
+        println!("Steps should be performed in the following order:\n\t{}", step_order);
+
+        // Steps can also be named with strings
+        let named = Dag::<String>::parse(&input.to_lines()).unwrap();
+        assert_eq!(named.order().unwrap().concat(), step_order);
+
+        // Invalid instructions are reported rather than scheduled
+        let invalid = [
+            "Step C must be finished before step A can begin.",
+            "Step A must be finished before step F can begin.",
+            "Step F must be finished before step C can begin.",
+            "Step A must be finished before step F can begin.",
+            "Step B must be finished before step B can begin.",
+        ].iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let errors = Dag::<char>::parse(&invalid).unwrap().order().unwrap_err();
+        assert_eq!(errors.0, vec![
+            DagError::SelfDependency('B'),
+            DagError::DuplicateDependency { node: 'F', dependency: 'A' },
+            DagError::Cycle(vec!['A', 'F', 'C']),
+        ]);
+        println!("{}", errors);
+    },
+    bench = |input: &Input| {
+        solve(&input.to_lines());
+    }
+}
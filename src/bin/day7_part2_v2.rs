@@ -0,0 +1,139 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod dag;
+use self::dag::*;
+
+use std::cmp::Reverse;
+
+// Settings ///////////////////////////////////////////////////////////////////
+
+const WORKERS: usize = 5;
+const BASE_DURATION: usize = 60; // Seconds added to every step
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find the time taken to complete a step; the base duration plus its position in the
+ alphabet (A=1, B=2, ...).
+*/
+fn duration(step: char, base: usize) -> usize {
+    base + (step as usize - 'A' as usize) + 1
+}
+
+/*
+ Create workers for comparing dispatch policies: identical workers, workers of differing
+ speeds, and workers where only some can work on the later half of the alphabet.
+*/
+fn worker_setups() -> Vec<(&'static str, Vec<Worker<char>>)> {
+    vec![
+        ("Identical", vec![Worker::new(); WORKERS]),
+        ("Mixed speeds", [200, 150, 100, 75, 50].iter()
+            .map(|&speed| Worker::new().with_speed(speed))
+            .collect()),
+        ("Specialists", (0..WORKERS)
+            .map(|w| if w < 2 { Worker::new() } else { Worker::new().with_skills('A'..='M') })
+            .collect()),
+    ]
+}
+
+/*
+ Find the time taken to complete all steps with 5 workers, where each step takes 60
+ seconds plus its position in the alphabet (A=1, B=2, ...).
+*/
+fn solve(instructions: &[String]) -> usize {
+    let steps = Dag::<char>::parse(instructions)
+        .unwrap_or_else(|e| panic!("{}", e));
+
+    steps.simulate(WORKERS, |&s| duration(s, BASE_DURATION), |_| ())
+        .unwrap_or_else(|e| panic!("{}", e))
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ Timings:
+    DEBUG: ~797us
+    RELEASE: ~74.7us
+*/
+run! {
+    input = "day7",
+    options = ["export-dot"], // e.g. `cargo run --bin day7_part2_v2 -- --export-dot res/other/day7.dot`
+    run = |input: &Input| {
+        let time_to_complete = solve(&input.to_lines());
+        assert_eq!(time_to_complete, 908);
+        println!("Total time to complete: {}", time_to_complete);
+
+        // Schedule example with 2 workers and no base duration
+        let example = [
+            "Step C must be finished before step A can begin.",
+            "Step C must be finished before step F can begin.",
+            "Step A must be finished before step B can begin.",
+            "Step A must be finished before step D can begin.",
+            "Step B must be finished before step E can begin.",
+            "Step D must be finished before step E can begin.",
+            "Step F must be finished before step E can begin.",
+        ].iter().map(|s| s.to_string()).collect::<Vec<String>>();
+        let example = Dag::<char>::parse(&example).unwrap()
+            .schedule(2, |&s| duration(s, 0), |_| ())
+            .unwrap();
+        assert_eq!(example.duration(), 15);
+        println!("\nExample:\n{}", example.to_table());
+
+        // Compare schedule against lower bounds
+        let steps = Dag::<char>::parse(&input.to_lines()).unwrap();
+        let schedule = steps.schedule(WORKERS, |&s| duration(s, BASE_DURATION), |_| ()).unwrap();
+        let (critical_path, critical_duration) = steps.critical_path(|&s| duration(s, BASE_DURATION)).unwrap();
+        let work_bound = schedule.work().div_ceil(WORKERS);
+        let lower_bound = critical_duration.max(work_bound);
+        assert_eq!(schedule.duration(), time_to_complete);
+
+        println!("{}", schedule.to_gantt(100));
+        for worker in 0..WORKERS {
+            let idle = schedule.idle_periods(worker).iter().map(|(from, to)| to - from).sum::<usize>();
+            println!("Worker {} idle for {} seconds", worker + 1, idle);
+        }
+        println!("Critical path: {} ({} seconds)", critical_path.iter().collect::<String>(), critical_duration);
+        println!("Lower bound: {} seconds (critical path), {} seconds (work split evenly between {} workers)",
+                 critical_duration, work_bound, WORKERS);
+        println!("{} workers take {:.1}% longer than the lower bound",
+                 WORKERS, (time_to_complete - lower_bound) as f64 / lower_bound as f64 * 100.0);
+
+        // Compare dispatch policies on the same graph
+        let policies = [Dispatch::Alphabetical, Dispatch::LongestFirst, Dispatch::CriticalPathFirst];
+        println!("\n{:<14}{:>14}{:>14}{:>19}", "Workers", "Alphabetical", "LongestFirst", "CriticalPathFirst");
+        for (name, workers) in worker_setups() {
+            print!("{:<14}", name);
+            for (&policy, width) in policies.iter().zip(&[14, 14, 19]) {
+                let urgency = steps.urgency(policy, |&s| duration(s, BASE_DURATION)).unwrap();
+                let schedule = steps.schedule_with(&workers, |&s| duration(s, BASE_DURATION), |s| Reverse(urgency[s]))
+                    .unwrap();
+                print!("{:>1$}", schedule.duration(), width);
+            }
+            println!();
+        }
+        let unskilled = [Worker::new().with_skills('A'..='M')];
+        match steps.schedule_with(&unskilled, |&s| duration(s, BASE_DURATION), |_| ()) {
+            Err(InvalidDag(errors)) => assert!(errors.contains(&DagError::Unassignable('Z'))),
+            Ok(_) => panic!("Steps N to Z can't be worked on"),
+        }
+
+        // Prioritise steps on the critical path, then the longest
+        let urgency = steps.urgency(Dispatch::LongestFirst, |&s| duration(s, BASE_DURATION)).unwrap();
+        let prioritised = steps.schedule(WORKERS, |&s| duration(s, BASE_DURATION),
+                                         |s| (!critical_path.contains(s), Reverse(urgency[s])))
+            .unwrap();
+        println!("Critical path steps first, then longest first: {} seconds", prioritised.duration());
+
+        // Export graph coloured by worker, with the critical path highlighted
+        if let Some(path) = input.option("export-dot") {
+            let dot = steps.to_dot(|&s| duration(s, BASE_DURATION), Some(&schedule), &critical_path);
+            std::fs::write(path, dot)
+                .unwrap_or_else(|e| panic!("Failed to write graph: {}", e));
+            println!("\nGraph written to {}", path);
+        }
+    },
+    bench = |input: &Input| {
+        solve(&input.to_lines());
+    }
+}
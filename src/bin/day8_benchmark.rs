@@ -0,0 +1,93 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod license_tree;
+use self::license_tree::*;
+
+use lazy_static::lazy_static;
+
+// Settings ///////////////////////////////////////////////////////////////////
+
+const GENERATED_DEPTH: usize = 1_000_000; // Nodes, each the only child of the last
+
+lazy_static! {
+    static ref GENERATED: Vec<usize> = generate(GENERATED_DEPTH);
+}
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Generate a tree of nested single children, where each node refers to its child, and has
+ one more metadata entry which isn't a valid reference. The innermost node has the value 1.
+ e.g. 1 2 1 2 0 1 1 1 3 1 3
+*/
+fn generate(depth: usize) -> Vec<usize> {
+    let mut numbers = Vec::with_capacity(depth * 4);
+    for _ in 1..depth {
+        numbers.extend(&[1, 2]);
+    }
+    numbers.extend(&[0, 1, 1]);
+    for _ in 1..depth {
+        numbers.extend(&[1, 3]);
+    }
+    numbers
+}
+
+fn to_string(numbers: &[usize]) -> String {
+    numbers.iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ Timings (puzzle input):
+    parse (recursive): DEBUG: ~5.84ms, RELEASE: ~633us
+    parse (stack, streamed): DEBUG: ~10.7ms, RELEASE: ~1.13ms
+    evaluate (recursive): DEBUG: ~4.31ms, RELEASE: ~390us
+    evaluate (stack): DEBUG: ~4.25ms, RELEASE: ~355us
+ Timings (1,000,000 nested nodes):
+    evaluate (stack, generated): DEBUG: ~406ms, RELEASE: ~51.9ms
+*/
+run! {
+    input = "day8",
+    run = |input: &Input| {
+
+        // Check both implementations against puzzle input
+        let numbers = input.raw().split_whitespace()
+            .map(|x| x.parse().unwrap())
+            .collect::<Vec<usize>>();
+        let info = evaluate(&numbers).unwrap();
+        assert_eq!(info, NodeInfo { size: numbers.len(), metadata_sum: 40848, value: 34466 });
+        assert_eq!(info, evaluate_recursive(&numbers).unwrap());
+        let tree = LicenseTree::parse(input.raw()).unwrap();
+        assert_eq!(tree, LicenseTree::parse_recursive(input.raw()).unwrap());
+        assert_eq!((tree.metadata_sum(), tree.value()), (40848, 34466));
+
+        // Check the stack implementations against a tree too deep to recurse
+        let info = evaluate(&GENERATED).unwrap();
+        let metadata_sum = 1 + (GENERATED_DEPTH - 1) * 4;
+        assert_eq!(info, NodeInfo { size: GENERATED.len(), metadata_sum, value: 1 });
+        let tree = LicenseTree::parse(&to_string(&GENERATED)).unwrap();
+        assert_eq!((tree.metadata_sum(), tree.value()), (metadata_sum, 1));
+        assert_eq!(tree.to_numbers(), *GENERATED);
+        let depth = tree.by_depth().last().map(|(depth, _)| depth).unwrap();
+        println!("Generated {} nodes to a depth of {}", tree.pre_order().count(), depth);
+        println!("Metadata sum: {}, value of root: {}", info.metadata_sum, info.value);
+    },
+    bench = {
+        "parse (recursive)" => |input: &Input| { LicenseTree::parse_recursive(input.raw()).unwrap(); },
+        "parse (stack, streamed)" => |input: &Input| { LicenseTree::parse(input.raw()).unwrap(); },
+        "evaluate (recursive)" => |input: &Input| {
+            let numbers = input.raw().split_whitespace().map(|x| x.parse().unwrap()).collect::<Vec<usize>>();
+            evaluate_recursive(&numbers).unwrap();
+        },
+        "evaluate (stack)" => |input: &Input| {
+            let numbers = input.raw().split_whitespace().map(|x| x.parse().unwrap()).collect::<Vec<usize>>();
+            evaluate(&numbers).unwrap();
+        },
+        "evaluate (stack, generated)" => |_: &Input| { evaluate(&GENERATED).unwrap(); },
+    }
+}
@@ -0,0 +1,50 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod license_tree;
+use self::license_tree::*;
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find the sum of all metadata entries.
+*/
+fn solve(tree: &str) -> usize {
+    LicenseTree::parse(tree)
+        .unwrap_or_else(|e| panic!("{}", e))
+        .metadata_sum()
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ Timings:
+    DEBUG: ~10.4ms
+    RELEASE: ~1.19ms
+*/
+run! {
+    input = "day8",
+    run = |input: &Input| {
+        let metadata_sum = solve(input.raw());
+        assert_eq!(metadata_sum, 40848);
+        println!("Metadata sum: {}", metadata_sum);
+
+        // Check tree is written back in the same format
+        let tree = LicenseTree::parse(input.raw()).unwrap();
+        assert_eq!(tree.to_string(), input.raw().split_whitespace().collect::<Vec<&str>>().join(" "));
+        assert_eq!(LicenseTree::parse(&tree.to_string()), Ok(tree.clone()));
+
+        // Describe shape of tree
+        let mut nodes_at_depth = Vec::new();
+        for (depth, _) in tree.by_depth() {
+            if depth == nodes_at_depth.len() { nodes_at_depth.push(0) }
+            nodes_at_depth[depth] += 1;
+        }
+        let leaves = tree.pre_order().filter(|n| n.children.is_empty()).count();
+        println!("Nodes: {} ({} leaves), nodes at each depth: {:?}",
+                 tree.pre_order().count(), leaves, nodes_at_depth);
+    },
+    bench = |input: &Input| {
+        solve(input.raw());
+    }
+}
@@ -0,0 +1,82 @@
+#[macro_use] mod common;
+use self::common::*;
+
+mod license_tree;
+use self::license_tree::*;
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find the value of the root node.
+*/
+fn solve(tree: &str) -> usize {
+    LicenseTree::parse(tree)
+        .unwrap_or_else(|e| panic!("{}", e))
+        .value()
+}
+
+// Entry Point ////////////////////////////////////////////////////////////////
+
+/*
+ Timings:
+    DEBUG: ~9.36ms
+    RELEASE: ~1.02ms
+*/
+run! {
+    input = "day8",
+    run = |input: &Input| {
+        let root_value = solve(input.raw());
+        assert_eq!(root_value, 34466);
+        println!("Value of root: {}", root_value);
+
+        // Example from the puzzle description
+        let example = LicenseTree::parse("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2").unwrap();
+        assert_eq!(example.metadata_sum(), 138);
+        assert_eq!(example.value(), 66);
+        let post_order = example.post_order().map(|n| n.value()).collect::<Vec<usize>>();
+        assert_eq!(post_order, vec![33, 99, 0, 66]);
+
+        // Find how many nodes contribute to the root's value
+        let tree = LicenseTree::parse(input.raw()).unwrap();
+        let zero_valued = tree.post_order().filter(|n| n.value() == 0).count();
+        println!("Nodes with no value: {} of {}", zero_valued, tree.pre_order().count());
+
+        // Remove every reference to a missing child; this doesn't change the value
+        let mut pruned = tree.clone();
+        let mut stack = vec![&mut pruned];
+        while let Some(node) = stack.pop() {
+            let num_children = node.children.len();
+            if num_children > 0 {
+                node.metadata.retain(|&m| m >= 1 && m <= num_children);
+            }
+            stack.extend(node.children.iter_mut());
+        }
+        assert_eq!(pruned.value(), root_value);
+        println!("Pruned missing references: {} to {} numbers",
+                 tree.to_numbers().len(), pruned.to_numbers().len());
+
+        // Stream tree from file
+        let file = std::fs::File::open(input.path())
+            .unwrap_or_else(|e| panic!("Failed to open {}: {}", input.path(), e));
+        let streamed = LicenseTree::from_reader(file).unwrap_or_else(|e| panic!("{}", e));
+        assert_eq!(streamed, tree);
+
+        // Report where malformed versions of the example go wrong
+        let malformed = [
+            ("2 3 0 3 10 11 12 1 1 0 1 99 2", ParseErrorKind::Truncated("metadata entry"), 29, vec![]),
+            ("2 3 0 3 10 11 12 1 1 0 1", ParseErrorKind::Truncated("metadata entry"), 24, vec![1, 0]),
+            ("2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2 7", ParseErrorKind::TrailingInput(String::from("7")), 36, vec![]),
+            ("2 3 0 3 10 1x 12 1 1 0 1 99 2 1 1 2", ParseErrorKind::InvalidNumber(String::from("1x")), 11, vec![0]),
+            ("2 3 0 3 10 11 12 1 99999999999999999999 0", ParseErrorKind::Overflow(String::from("99999999999999999999")), 19, vec![1]),
+        ];
+        println!();
+        for (input, kind, offset, path) in malformed.iter().cloned() {
+            let error = LicenseTree::parse(input).unwrap_err();
+            assert_eq!(error, ParseError { kind, offset, path });
+            println!("{:<42} {}", input, error);
+        }
+    },
+    bench = |input: &Input| {
+        solve(input.raw());
+    }
+}
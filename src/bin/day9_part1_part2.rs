@@ -1,8 +1,48 @@
 #[macro_use] mod common;
 use self::common::*;
 
+// Settings ///////////////////////////////////////////////////////////////////
+
+const PART_2_MULTIPLIER: usize = 100; // Times larger the last marble is in part 2
+
 // Types //////////////////////////////////////////////////////////////////////
 
+/*
+ The parameters of a marble game, and the winning score if known.
+*/
+#[derive(Debug, PartialEq)]
+struct Game {
+    players: usize,
+    last_marble: usize,
+    high_score: Option<usize>,
+}
+
+impl Game {
+
+    /*
+     Parse a game from a description in either of the following formats:
+         10 players; last marble is worth 1618 points
+         10 players; last marble is worth 1618 points: high score is 8317
+    */
+    fn parse(description: &str) -> Result<Game, String> {
+        let invalid = || format!("Invalid game: {}", description);
+        let number = |word: &str| word.parse::<usize>().map_err(|_| invalid());
+        let words = description.split_whitespace().collect::<Vec<&str>>();
+
+        let game = match words[..] {
+            [players, "players;", "last", "marble", "is", "worth", last_marble, "points"] =>
+                Game { players: number(players)?, last_marble: number(last_marble)?, high_score: None },
+            [players, "players;", "last", "marble", "is", "worth", last_marble, "points:", "high", "score", "is", high_score] =>
+                Game { players: number(players)?, last_marble: number(last_marble)?, high_score: Some(number(high_score)?) },
+            _ => return Err(invalid()),
+        };
+        if game.players == 0 {
+            return Err(format!("A game needs at least one player: {}", description))
+        }
+        Ok(game)
+    }
+}
+
 struct MarbleNode {
     value: usize,
     clockwise_i: usize,
@@ -142,6 +182,14 @@ fn play(players: usize, marbles: usize) -> usize {
     *player_scores.iter().max().unwrap()
 }
 
+/*
+ Find the winning score of a game, where the last marble is worth some multiple of the
+ points it's described with.
+*/
+fn solve(game: &Game, multiplier: usize) -> usize {
+    play(game.players, (game.last_marble * multiplier) + 1)
+}
+
 // Entry Point ////////////////////////////////////////////////////////////////
 
 /*
@@ -151,17 +199,34 @@ fn play(players: usize, marbles: usize) -> usize {
 */
 run! {
     input = "day9",
-    run = |_: &Input| {
-        let winning_score_a = play(416, 71617 + 1);
+    run = |input: &Input| {
+
+        // Check examples from the puzzle description
+        let examples = [
+            "9 players; last marble is worth 25 points: high score is 32",
+            "10 players; last marble is worth 1618 points: high score is 8317",
+            "13 players; last marble is worth 7999 points: high score is 146373",
+            "17 players; last marble is worth 1104 points: high score is 2764",
+            "21 players; last marble is worth 6111 points: high score is 54718",
+            "30 players; last marble is worth 5807 points: high score is 37305",
+        ];
+        for example in examples.iter() {
+            let game = Game::parse(example).unwrap();
+            assert_eq!(Some(solve(&game, 1)), game.high_score, "{}", example);
+        }
+
+        let game = Game::parse(input.raw()).unwrap_or_else(|e| panic!("{}", e));
+        let winning_score_a = solve(&game, 1);
         assert_eq!(winning_score_a, 436720);
         println!("Winning Score A: {}", winning_score_a);
 
-        let winning_score_b = play(416, (71617*100) + 1);
+        let winning_score_b = solve(&game, PART_2_MULTIPLIER);
         assert_eq!(winning_score_b, 3527845091);
         println!("Winning Score B: {}", winning_score_b);
     },
-    bench = |_: &Input| {
-        play(416, 71617 + 1);
-        play(416, (71617*100) + 1);
+    bench = |input: &Input| {
+        let game = Game::parse(input.raw()).unwrap();
+        solve(&game, 1);
+        solve(&game, PART_2_MULTIPLIER);
     }
-}
\ No newline at end of file
+}
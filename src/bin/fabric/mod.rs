@@ -0,0 +1,451 @@
+
+// Claim //////////////////////////////////////////////////////////////////////
+
+/*
+ A rectangular claim on a sheet of fabric.
+ Coordinates are unbounded and may be negative.
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Claim {
+    pub id: i32,
+    pub x: i64,
+    pub y: i64,
+    pub width: i64,
+    pub height: i64,
+}
+
+impl Claim {
+
+    /*
+     Parse a claim from a string in the following format:
+         #1 @ 509,796: 18x15
+    */
+    pub fn parse(claim: &str) -> Claim {
+        let fields: Vec<i64> = claim.chars()
+            .map(|c| match c {
+                '#'|'@'|','|':'|'x' => ' ',
+                _ => c
+            })
+            .collect::<String>()
+            .split_whitespace()
+            .map(|p| p.parse().unwrap())
+            .collect();
+
+        assert_eq!(fields.len(), 5, "Unable to parse claim: {}", claim);
+        assert!(fields[3] > 0 && fields[4] > 0, "Claim must have a positive area: {}", claim);
+
+        Claim {
+            id: fields[0] as i32,
+            x: fields[1],
+            y: fields[2],
+            width: fields[3],
+            height: fields[4],
+        }
+    }
+
+    /*
+     Get the exclusive right edge of the claim.
+    */
+    pub fn right(&self) -> i64 {
+        self.x + self.width
+    }
+
+    /*
+     Get the exclusive bottom edge of the claim.
+    */
+    pub fn bottom(&self) -> i64 {
+        self.y + self.height
+    }
+
+    /*
+     Check if a square inch is part of this claim.
+    */
+    #[allow(dead_code)]
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        self.x <= x && x < self.right() && self.y <= y && y < self.bottom()
+    }
+
+    /*
+     Find the area shared by this claim and another.
+     Returns 0 if the claims do not overlap.
+    */
+    #[allow(dead_code)]
+    pub fn overlap_area(&self, other: &Claim) -> i64 {
+        let width = self.right().min(other.right()) - self.x.max(other.x);
+        let height = self.bottom().min(other.bottom()) - self.y.max(other.y);
+        width.max(0) * height.max(0)
+    }
+}
+
+// FabricSheet ////////////////////////////////////////////////////////////////
+
+/*
+ The number of claims covering each square inch of fabric.
+
+ Only the coordinates where claims start or end are kept, dividing the fabric into cells
+ which are covered by the same claims throughout. Its size therefore depends only on the
+ number of claims, and not on how far apart they are.
+*/
+pub struct FabricSheet {
+    xs: Vec<i64>,     // Sorted, unique x-coordinates of claim edges. Column i spans xs[i]..xs[i+1]
+    ys: Vec<i64>,     // Sorted, unique y-coordinates of claim edges. Row j spans ys[j]..ys[j+1]
+    counts: Vec<u32>, // Claims covering each cell, row by row
+}
+
+#[allow(dead_code)]
+impl FabricSheet {
+
+    /*
+     Mark all claims on a new sheet of fabric.
+    */
+    pub fn from_claims(claims: &[Claim]) -> Result<FabricSheet, String> {
+        let edges = |from: fn(&Claim) -> i64, to: fn(&Claim) -> i64| {
+            let mut edges = claims.iter()
+                .flat_map(|c| vec![from(c), to(c)])
+                .collect::<Vec<i64>>();
+            edges.sort();
+            edges.dedup();
+            edges
+        };
+        let xs = edges(|c| c.x, Claim::right);
+        let ys = edges(|c| c.y, Claim::bottom);
+        let columns = xs.len().saturating_sub(1);
+        let rows = ys.len().saturating_sub(1);
+        let cells = columns.checked_mul(rows)
+            .ok_or_else(|| format!("Too many claims to mark on a sheet: {}", claims.len()))?;
+
+        // Mark where each claim starts and stops covering cells
+        let mut marks = vec![0i32; cells];
+        for claim in claims {
+            let (left, right) = (Self::edge(&xs, claim.x), Self::edge(&xs, claim.right()));
+            let (top, bottom) = (Self::edge(&ys, claim.y), Self::edge(&ys, claim.bottom()));
+            for &(column, row, delta) in &[(left, top, 1), (right, top, -1), (left, bottom, -1), (right, bottom, 1)] {
+                if column < columns && row < rows {
+                    marks[row * columns + column] += delta;
+                }
+            }
+        }
+
+        // Sum marks across and down
+        for row in 0..rows {
+            for column in 0..columns {
+                let i = row * columns + column;
+                if column > 0 { marks[i] += marks[i - 1] }
+                if row > 0 { marks[i] += marks[i - columns] }
+                if column > 0 && row > 0 { marks[i] -= marks[i - columns - 1] }
+            }
+        }
+
+        let counts = marks.into_iter().map(|count| count as u32).collect();
+        Ok(FabricSheet { xs, ys, counts })
+    }
+
+    fn edge(edges: &[i64], at: i64) -> usize {
+        edges.binary_search(&at).unwrap()
+    }
+
+    /*
+     Find the cell containing a coordinate, if it's between the first and last edges.
+    */
+    fn cell(edges: &[i64], at: i64) -> Option<usize> {
+        let after = edges.partition_point(|&edge| edge <= at);
+        if after == 0 || after == edges.len() { None } else { Some(after - 1) }
+    }
+
+    /*
+     Get the smallest rectangle containing every claim, as (left, top, right, bottom) with
+     the right and bottom edges exclusive. Returns None if there are no claims.
+    */
+    pub fn bounds(&self) -> Option<(i64, i64, i64, i64)> {
+        Some((*self.xs.first()?, *self.ys.first()?, *self.xs.last()?, *self.ys.last()?))
+    }
+
+    /*
+     Get the number of claims covering a square inch.
+     Square inches outside the sheet are unclaimed.
+    */
+    pub fn count_at(&self, x: i64, y: i64) -> u32 {
+        match (Self::cell(&self.xs, x), Self::cell(&self.ys, y)) {
+            (Some(column), Some(row)) => self.counts[row * (self.xs.len() - 1) + column],
+            _ => 0,
+        }
+    }
+
+    /*
+     Get the largest number of claims covering any square inch.
+    */
+    pub fn max_count(&self) -> u32 {
+        self.counts.iter().cloned().max().unwrap_or(0)
+    }
+}
+
+// Sweep Line /////////////////////////////////////////////////////////////////
+
+/*
+ A segment tree over the compressed x-coordinates of all claims.
+
+ Each node records how many claims cover its entire segment, as well as the
+ length of its segment which is covered by at least one and at least two claims.
+ Covered lengths only need updating along the path of each insertion/removal.
+*/
+#[allow(dead_code)]
+struct CoverageTree {
+    xs: Vec<i64>,     // Sorted, unique x-coordinates. Leaf i spans xs[i]..xs[i+1]
+    count: Vec<u32>,  // Claims covering the entire segment of each node
+    once: Vec<i64>,   // Length covered by at least one claim
+    twice: Vec<i64>,  // Length covered by at least two claims
+}
+
+#[allow(dead_code)]
+impl CoverageTree {
+
+    fn new(xs: Vec<i64>) -> CoverageTree {
+        let nodes = 4 * xs.len().max(1);
+        CoverageTree {
+            xs,
+            count: vec![0; nodes],
+            once: vec![0; nodes],
+            twice: vec![0; nodes],
+        }
+    }
+
+    /*
+     Add or remove a claim spanning from..to.
+    */
+    fn update(&mut self, from: i64, to: i64, delta: i32) {
+        let from = self.xs.binary_search(&from).unwrap();
+        let to = self.xs.binary_search(&to).unwrap();
+        let leaves = self.xs.len() - 1;
+        self.update_node(1, 0, leaves, from, to, delta);
+    }
+
+    /*
+     Apply an update to the leaves from..to within the node spanning lo..hi.
+    */
+    fn update_node(&mut self, node: usize, lo: usize, hi: usize,
+                   from: usize, to: usize, delta: i32) {
+        if to <= lo || hi <= from {
+            return // No intersection
+        }
+
+        if from <= lo && hi <= to {
+            // Node entirely covered
+            self.count[node] = (self.count[node] as i32 + delta) as u32;
+        } else {
+            // Node partially covered
+            let mid = (lo + hi) / 2;
+            self.update_node(node * 2, lo, mid, from, to, delta);
+            self.update_node(node * 2 + 1, mid, hi, from, to, delta);
+        }
+
+        self.pull(node, lo, hi);
+    }
+
+    /*
+     Recalculate the covered lengths of a node from its count and its children.
+    */
+    fn pull(&mut self, node: usize, lo: usize, hi: usize) {
+        let length = self.xs[hi] - self.xs[lo];
+        let is_leaf = hi - lo == 1;
+        let (child_once, child_twice) = if is_leaf {
+            (0, 0)
+        } else {
+            (self.once[node * 2] + self.once[node * 2 + 1],
+             self.twice[node * 2] + self.twice[node * 2 + 1])
+        };
+
+        match self.count[node] {
+            0 => {
+                self.once[node] = child_once;
+                self.twice[node] = child_twice;
+            },
+            1 => {
+                self.once[node] = length;
+                self.twice[node] = child_once; // Anything else covering is a second claim
+            },
+            _ => {
+                self.once[node] = length;
+                self.twice[node] = length;
+            }
+        }
+    }
+
+    /*
+     Get the total length covered by at least two claims.
+    */
+    fn covered_twice(&self) -> i64 {
+        self.twice[1]
+    }
+}
+
+/*
+ Find the total area covered by two or more claims.
+
+ A horizontal line is swept from top to bottom, stopping at the top and bottom
+ edge of each claim. Between stops the doubly-covered width remains constant,
+ so each band contributes its width multiplied by its height.
+*/
+#[allow(dead_code)]
+pub fn find_overlap_area(claims: &[Claim]) -> i64 {
+    if claims.is_empty() {
+        return 0
+    }
+
+    // Compress x-coordinates
+    let mut xs = claims.iter()
+        .flat_map(|c| vec![c.x, c.right()])
+        .collect::<Vec<i64>>();
+    xs.sort();
+    xs.dedup();
+
+    // Claims start and end at their top and bottom edges
+    let mut events = claims.iter()
+        .flat_map(|c| vec![(c.y, 1, c.x, c.right()), (c.bottom(), -1, c.x, c.right())])
+        .collect::<Vec<(i64, i32, i64, i64)>>();
+    events.sort();
+
+    // Sweep
+    let mut tree = CoverageTree::new(xs);
+    let mut area = 0;
+    let mut last_y = events[0].0;
+    for (y, delta, from, to) in events {
+        area += tree.covered_twice() * (y - last_y);
+        tree.update(from, to, delta);
+        last_y = y;
+    }
+
+    area
+}
+
+// Dominance Counting /////////////////////////////////////////////////////////
+
+/*
+ A Fenwick tree (binary indexed tree) of counts.
+*/
+#[allow(dead_code)]
+struct FenwickTree {
+    counts: Vec<usize>,
+}
+
+#[allow(dead_code)]
+impl FenwickTree {
+
+    fn new(size: usize) -> FenwickTree {
+        FenwickTree { counts: vec![0; size + 1] }
+    }
+
+    /*
+     Increment the count at the given index.
+    */
+    fn increment(&mut self, index: usize) {
+        let mut i = index + 1;
+        while i < self.counts.len() {
+            self.counts[i] += 1;
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /*
+     Sum the counts of the first n indices.
+    */
+    fn prefix_sum(&self, n: usize) -> usize {
+        let mut sum = 0;
+        let mut i = n;
+        while i > 0 {
+            sum += self.counts[i];
+            i -= i & i.wrapping_neg();
+        }
+        sum
+    }
+}
+
+/*
+ For each query (qx, qy), count the points (px, py) where px <= qx and py <= qy.
+
+ Points and queries are both swept in order of x, while a Fenwick tree over the
+ compressed y-coordinates counts the points seen so far.
+*/
+#[allow(dead_code)]
+fn count_dominated(points: &[(i64, i64)], queries: &[(i64, i64)]) -> Vec<usize> {
+    let mut points = points.to_vec();
+    points.sort();
+
+    let mut ys = points.iter().map(|p| p.1).collect::<Vec<i64>>();
+    ys.sort();
+    ys.dedup();
+
+    let mut order = (0..queries.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|&i| queries[i]);
+
+    let mut tree = FenwickTree::new(ys.len());
+    let mut counts = vec![0; queries.len()];
+    let mut next_point = points.iter().peekable();
+    for i in order {
+        let (qx, qy) = queries[i];
+
+        // Add all points to the left of this query
+        while let Some(&&(px, py)) = next_point.peek() {
+            if px > qx { break }
+            tree.increment(ys.binary_search(&py).unwrap());
+            next_point.next();
+        }
+
+        // Count those also below this query
+        let below = ys.partition_point(|&y| y <= qy);
+        counts[i] = tree.prefix_sum(below);
+    }
+
+    counts
+}
+
+/*
+ Count the number of claims which overlap each claim (excluding itself).
+
+ Rather than test every pair, count the claims which are disjoint from each claim.
+ A disjoint claim lies entirely to the left, right, above, or below; with those
+ in a corner (e.g. left and above) counted twice. Each of these is a dominance
+ count (after negating coordinates as needed), so by inclusion-exclusion:
+
+    overlapping = n - (left + right + above + below)
+                    + (left·above + left·below + right·above + right·below)
+*/
+#[allow(dead_code)]
+pub fn count_overlapping(claims: &[Claim]) -> Vec<usize> {
+    let count = |point: &dyn Fn(&Claim) -> (i64, i64), query: &dyn Fn(&Claim) -> (i64, i64)| {
+        let points = claims.iter().map(point).collect::<Vec<_>>();
+        let queries = claims.iter().map(query).collect::<Vec<_>>();
+        count_dominated(&points, &queries)
+    };
+
+    // Disjoint along one axis
+    let left = count(&|c| (c.right(), 0), &|c| (c.x, 0));
+    let right = count(&|c| (-c.x, 0), &|c| (-c.right(), 0));
+    let above = count(&|c| (c.bottom(), 0), &|c| (c.y, 0));
+    let below = count(&|c| (-c.y, 0), &|c| (-c.bottom(), 0));
+
+    // Disjoint along both axes
+    let left_above = count(&|c| (c.right(), c.bottom()), &|c| (c.x, c.y));
+    let left_below = count(&|c| (c.right(), -c.y), &|c| (c.x, -c.bottom()));
+    let right_above = count(&|c| (-c.x, c.bottom()), &|c| (-c.right(), c.y));
+    let right_below = count(&|c| (-c.x, -c.y), &|c| (-c.right(), -c.bottom()));
+
+    (0..claims.len())
+        .map(|i| {
+            let disjoint = left[i] + right[i] + above[i] + below[i]
+                - left_above[i] - left_below[i] - right_above[i] - right_below[i];
+            claims.len() - disjoint - 1 // Every claim overlaps itself
+        })
+        .collect()
+}
+
+/*
+ Find all claims which do not overlap any other claim.
+*/
+#[allow(dead_code)]
+pub fn find_non_overlapping(claims: &[Claim]) -> Vec<&Claim> {
+    claims.iter()
+        .zip(count_overlapping(claims))
+        .filter(|(_, overlapping)| *overlapping == 0)
+        .map(|(claim, _)| claim)
+        .collect()
+}
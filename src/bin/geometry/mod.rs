@@ -0,0 +1,727 @@
+
+// Point //////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+
+    pub fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    /*
+     Parse a point from a string in the following format:
+         137, 140
+    */
+    pub fn parse(point: &str) -> Result<Point, String> {
+        let invalid = || format!("Invalid point: {}", point);
+
+        let coords = point.split(',')
+            .map(|c| c.trim().parse::<i32>().map_err(|_| invalid()))
+            .collect::<Result<Vec<i32>, String>>()?;
+
+        if let [x, y] = coords[..] { Ok(Point { x, y }) } else { Err(invalid()) }
+    }
+
+    /*
+     Get the manhattan (taxicab) distance between two points.
+    */
+    pub fn manhattan_dist(&self, other: &Point) -> i64 {
+        (self.x as i64 - other.x as i64).abs() + (self.y as i64 - other.y as i64).abs()
+    }
+}
+
+// Rect ///////////////////////////////////////////////////////////////////////
+
+/*
+ An axis-aligned rectangle of integer coordinates, inclusive of both corners.
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rect {
+    pub from: Point,
+    pub to: Point,
+}
+
+impl Rect {
+
+    /*
+     Find the smallest rectangle which contains all given points.
+     Returns None if there are no points.
+    */
+    pub fn bounding(points: &[Point]) -> Option<Rect> {
+        let first = *points.first()?;
+        let (mut from, mut to) = (first, first);
+        for p in points {
+            from = Point::new(from.x.min(p.x), from.y.min(p.y));
+            to = Point::new(to.x.max(p.x), to.y.max(p.y));
+        }
+        Some(Rect { from, to })
+    }
+
+    #[allow(dead_code)]
+    pub fn width(&self) -> usize {
+        (self.to.x - self.from.x + 1) as usize
+    }
+
+    #[allow(dead_code)]
+    pub fn height(&self) -> usize {
+        (self.to.y - self.from.y + 1) as usize
+    }
+
+    /*
+     Grow the rectangle by the same amount on every side.
+    */
+    #[allow(dead_code)]
+    pub fn expand(&self, by: i32) -> Rect {
+        Rect {
+            from: Point::new(self.from.x - by, self.from.y - by),
+            to: Point::new(self.to.x + by, self.to.y + by),
+        }
+    }
+
+    #[allow(dead_code)]
+    pub fn contains(&self, point: &Point) -> bool {
+        point.x >= self.from.x && point.x <= self.to.x &&
+            point.y >= self.from.y && point.y <= self.to.y
+    }
+
+    #[allow(dead_code)]
+    pub fn is_on_boundary(&self, point: &Point) -> bool {
+        let x_bound = point.x == self.from.x || point.x == self.to.x;
+        let y_bound = point.y == self.from.y || point.y == self.to.y;
+        x_bound || y_bound
+    }
+}
+
+// Metric /////////////////////////////////////////////////////////////////////
+
+/*
+ A way of measuring the distance between two points.
+
+ Every metric here is a norm (or the square of one), so the summed distance from a
+ point to a set of sites is convex, and is at least the number of sites times the
+ distance to their centroid.
+*/
+pub trait Metric {
+
+    /*
+     Get the distance between two points.
+    */
+    fn dist(&self, a: &Point, b: &Point) -> i64;
+
+    /*
+     Get a secondary distance used to decide between points equally distant from another.
+     Points are only tied if both distances are equal. By default every tie is kept.
+    */
+    fn tie_break(&self, _a: &Point, _b: &Point) -> i64 {
+        0
+    }
+
+    /*
+     Get the furthest a point can be from another along either axis while within max_dist.
+    */
+    fn reach(&self, max_dist: i64) -> i64;
+
+    /*
+     Find the sites whose regions contain infinitely many coordinates, in ascending order.
+    */
+    fn infinite_sites(&self, sites: &[Point]) -> Vec<usize>;
+}
+
+/*
+ Distance when moving along either axis, one at a time. i.e. |dx| + |dy|
+*/
+pub struct Manhattan;
+
+impl Metric for Manhattan {
+    fn dist(&self, a: &Point, b: &Point) -> i64 {
+        a.manhattan_dist(b)
+    }
+
+    fn reach(&self, max_dist: i64) -> i64 {
+        max_dist
+    }
+
+    fn infinite_sites(&self, sites: &[Point]) -> Vec<usize> {
+        manhattan_infinite_sites(sites)
+    }
+}
+
+/*
+ Distance when moving along both axes at once, as a king does in chess. i.e. max(|dx|, |dy|)
+
+ Points at the same distance from two sites can cover whole areas rather than lines,
+ so ties are broken by the manhattan distance; preferring the site reached with
+ fewer diagonal moves.
+*/
+pub struct Chebyshev;
+
+impl Metric for Chebyshev {
+    fn dist(&self, a: &Point, b: &Point) -> i64 {
+        let dx = (a.x as i64 - b.x as i64).abs();
+        let dy = (a.y as i64 - b.y as i64).abs();
+        dx.max(dy)
+    }
+
+    fn tie_break(&self, a: &Point, b: &Point) -> i64 {
+        a.manhattan_dist(b)
+    }
+
+    fn reach(&self, max_dist: i64) -> i64 {
+        max_dist
+    }
+
+    /*
+     Far along an axis the distance to each site is just how far behind it is, so the
+     furthest site ahead is closest, and only sites level with it are decided by the tie break.
+     Far along a diagonal, in coordinates rotated by 45° the distance is half the manhattan
+     distance, with ties going to the site furthest ahead. Regions can be unbounded along
+     a diagonal without being unbounded along either axis, so both are searched.
+    */
+    fn infinite_sites(&self, sites: &[Point]) -> Vec<usize> {
+        let axis = |ahead: i64| match ahead.signum() {
+            1 => i64::MAX,
+            -1 => i64::MIN,
+            _ => 0,
+        };
+        let diagonal = |ahead: i64| if ahead > 0 { ahead + 1 } else { ahead };
+        let directions = AXES.iter().map(|along| (along, &axis as &dyn Fn(i64) -> i64))
+            .chain(DIAGONALS.iter().map(|along| (along, &diagonal as &dyn Fn(i64) -> i64)))
+            .collect::<Vec<_>>();
+        unbounded_sites(sites, &directions)
+    }
+}
+
+/*
+ Square of the straight line distance. i.e. dx² + dy²
+ Kept squared so distances, and therefore ties, are exact.
+*/
+pub struct SquaredEuclidean;
+
+impl Metric for SquaredEuclidean {
+    fn dist(&self, a: &Point, b: &Point) -> i64 {
+        let dx = a.x as i64 - b.x as i64;
+        let dy = a.y as i64 - b.y as i64;
+        dx * dx + dy * dy
+    }
+
+    fn reach(&self, max_dist: i64) -> i64 {
+        (max_dist.max(0) as f64).sqrt() as i64 + 1
+    }
+
+    fn infinite_sites(&self, sites: &[Point]) -> Vec<usize> {
+        convex_hull_sites(sites)
+    }
+}
+
+/*
+ Manhattan distance where moving along each axis has a different cost. i.e. x·|dx| + y·|dy|
+*/
+pub struct WeightedManhattan {
+    x: i64,
+    y: i64,
+}
+
+impl WeightedManhattan {
+
+    /*
+     Create a metric with the given cost per step along each axis, which must be positive.
+    */
+    #[allow(dead_code)]
+    pub fn new(x: i64, y: i64) -> WeightedManhattan {
+        assert!(x > 0 && y > 0, "Weights must be positive");
+        WeightedManhattan { x, y }
+    }
+}
+
+impl Metric for WeightedManhattan {
+    fn dist(&self, a: &Point, b: &Point) -> i64 {
+        let dx = (a.x as i64 - b.x as i64).abs();
+        let dy = (a.y as i64 - b.y as i64).abs();
+        self.x * dx + self.y * dy
+    }
+
+    fn reach(&self, max_dist: i64) -> i64 {
+        max_dist / self.x.min(self.y)
+    }
+
+    /*
+     Far along an axis, being one step further ahead is worth as many steps across the
+     axis as the ratio of their weights.
+    */
+    fn infinite_sites(&self, sites: &[Point]) -> Vec<usize> {
+        let ceil_div = |a: i64, b: i64| -(-a).div_euclid(b);
+        let along_x = |ahead: i64| ceil_div(self.x * ahead, self.y);
+        let along_y = |ahead: i64| ceil_div(self.y * ahead, self.x);
+        unbounded_sites(sites, &[
+            (&AXES[0], &along_x),
+            (&AXES[1], &along_x),
+            (&AXES[2], &along_y),
+            (&AXES[3], &along_y),
+        ])
+    }
+}
+
+// Voronoi ////////////////////////////////////////////////////////////////////
+
+/*
+ The integer coordinates closer to one site than any other.
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Region {
+    pub area: usize,     // Only counts coordinates within the bounding rectangle if infinite
+    pub infinite: bool,
+}
+
+/*
+ The sites with the smallest value of some function, and how many there are.
+*/
+#[derive(Copy, Clone)]
+struct Closest {
+    value: i64,
+    site: usize,
+    count: usize,
+}
+
+impl Closest {
+
+    const NONE: Closest = Closest { value: i64::MAX, site: 0, count: 0 };
+
+    fn with(self, value: i64, site: usize) -> Closest {
+        if value < self.value {
+            Closest { value, site, count: 1 }
+        } else if value == self.value {
+            Closest { count: self.count + 1, ..self }
+        } else {
+            self
+        }
+    }
+
+    /*
+     Offset the value of every site by the same amount.
+    */
+    #[allow(dead_code)]
+    fn shift(self, by: i64) -> Closest {
+        Closest { value: self.value + by, ..self }
+    }
+
+    /*
+     Get the unique closest site out of both sets of sites, if any.
+     Both sets are allowed to contain the same site.
+    */
+    #[allow(dead_code)]
+    fn owner(left: Closest, right: Closest) -> Option<usize> {
+        if left.value < right.value {
+            if left.count == 1 { Some(left.site) } else { None }
+        } else if right.value < left.value {
+            if right.count == 1 { Some(right.site) } else { None }
+        } else if left.count == 1 && right.count == 1 && left.site == right.site {
+            Some(left.site)
+        } else {
+            None
+        }
+    }
+}
+
+/*
+ Count the integers in the range from..=to.
+*/
+#[allow(dead_code)]
+fn count_between(from: i64, to: i64) -> usize {
+    if to < from { 0 } else { (to - from + 1) as usize }
+}
+
+/*
+ Find the region of every site under the manhattan metric, where coordinates equally
+ close to multiple sites belong to none.
+
+ A horizontal line is swept down the bounding rectangle of all sites one row at a time.
+ Along a row each site's distance is a "V" shape |x - site.x| + |y - site.y|, so
+ within each gap between adjacent site columns the closest site on the left is found by
+ a prefix minimum of (dy - site.x), and on the right by a suffix minimum of (dy + site.x).
+ Where the left and right distances cross can then be solved directly, so each row takes
+ time proportional to the number of sites rather than its width.
+
+ This is a row sweep rather than a construction of the diagram's regions from the sites
+ (as Fortune's algorithm does), so it takes O(sites × rows) time and still grows with the
+ height of the bounding rectangle.
+
+ Infinite regions are found separately by manhattan_infinite_sites(), and only the
+ coordinates within the bounding rectangle are counted for them.
+*/
+#[allow(dead_code)]
+pub fn manhattan_voronoi(sites: &[Point]) -> Vec<Region> {
+    let mut regions = vec![Region { area: 0, infinite: false }; sites.len()];
+    let bound = match Rect::bounding(sites) {
+        Some(bound) => bound,
+        None => return regions,
+    };
+
+    // Group sites into columns from left to right
+    let mut order = (0..sites.len()).collect::<Vec<usize>>();
+    order.sort_by_key(|&i| sites[i].x);
+    let mut columns: Vec<(i64, Vec<usize>)> = Vec::new();
+    for i in order {
+        let x = sites[i].x as i64;
+        match columns.last_mut() {
+            Some((column_x, column)) if *column_x == x => column.push(i),
+            _ => columns.push((x, vec![i])),
+        }
+    }
+
+    let mut prefix = vec![Closest::NONE; columns.len()];
+    let mut suffix = vec![Closest::NONE; columns.len()];
+    for y in bound.from.y..=bound.to.y {
+        let dy = |i: usize| (sites[i].y as i64 - y as i64).abs();
+
+        // Find closest sites at or to the left/right of each column, ignoring x
+        let mut closest = Closest::NONE;
+        for (c, (x, column)) in columns.iter().enumerate() {
+            closest = column.iter().fold(closest, |closest, &i| closest.with(dy(i) - x, i));
+            prefix[c] = closest;
+        }
+        let mut closest = Closest::NONE;
+        for (c, (x, column)) in columns.iter().enumerate().rev() {
+            closest = column.iter().fold(closest, |closest, &i| closest.with(dy(i) + x, i));
+            suffix[c] = closest;
+        }
+
+        let mut claim = |owner: Option<usize>, cells: usize| {
+            if let Some(owner) = owner {
+                regions[owner].area += cells;
+            }
+        };
+
+        for c in 0..columns.len() {
+
+            // Coordinate within a column of sites
+            let x = columns[c].0;
+            let owner = Closest::owner(prefix[c].shift(x), suffix[c].shift(-x));
+            claim(owner, 1);
+
+            // Coordinates within the gap to the next column
+            // Left is closer while x + left < right - x, i.e. 2x < right - left
+            if c + 1 == columns.len() { continue }
+            let (left, right) = (prefix[c], suffix[c + 1]);
+            let (from, to) = (x + 1, columns[c + 1].0 - 1);
+            let crossing = right.value - left.value;
+            let last_left = (crossing - 1).div_euclid(2);
+            let first_right = crossing.div_euclid(2) + 1;
+
+            let left_owner = if left.count == 1 { Some(left.site) } else { None };
+            let right_owner = if right.count == 1 { Some(right.site) } else { None };
+            claim(left_owner, count_between(from, to.min(last_left)));
+            claim(right_owner, count_between(from.max(first_right), to));
+        }
+    }
+
+    for site in manhattan_infinite_sites(sites) {
+        regions[site].infinite = true;
+    }
+    regions
+}
+
+/*
+ Maps a point to (u, v) coordinates along and across a direction.
+*/
+type Projection = fn(&Point) -> (i64, i64);
+
+const AXES: [Projection; 4] = [
+    |p| (p.x as i64, p.y as i64),   // Right
+    |p| (-p.x as i64, p.y as i64),  // Left
+    |p| (p.y as i64, p.x as i64),   // Down
+    |p| (-p.y as i64, p.x as i64),  // Up
+];
+
+const DIAGONALS: [Projection; 4] = [
+    |p| (p.x as i64 + p.y as i64, p.x as i64 - p.y as i64),     // Down and right
+    |p| (-(p.x as i64 + p.y as i64), p.x as i64 - p.y as i64),  // Up and left
+    |p| (p.x as i64 - p.y as i64, p.x as i64 + p.y as i64),     // Up and right
+    |p| (p.y as i64 - p.x as i64, p.x as i64 + p.y as i64),     // Down and left
+];
+
+/*
+ Find the sites whose regions are infinite under the manhattan metric, in ascending order.
+
+ A region is infinite only if it contains coordinates arbitrarily far away in one of the
+ four axis directions. e.g. far to the right, the distance to each site along a row is
+ x - site.x + |y - site.y|, so which site is closest depends only on the row and not on x.
+ A site s is therefore unbounded to the right if there's some row where |y - s.y| - s.x
+ is lower than for every other site.
+
+ Against each other site t, the rows where s is closer form an interval:
+ - If t lies within the 90° cone opening to the right of s (t.x - s.x >= |t.y - s.y|),
+   t is at least as close on every row, and dominates s completely.
+ - If s lies within the cone opening to the right of t, s is closer on every row.
+ - Otherwise s is closer on the rows to one side of where their distances cross.
+ The region is unbounded to the right if the intersection of these intervals is non-empty.
+ This is the manhattan equivalent of a site lying on the convex hull of the sites
+ for the euclidean metric, and doesn't depend on any bounding rectangle.
+*/
+pub fn manhattan_infinite_sites(sites: &[Point]) -> Vec<usize> {
+    let lead = |ahead: i64| ahead;
+    let directions = AXES.iter()
+        .map(|along| (along, &lead as &dyn Fn(i64) -> i64))
+        .collect::<Vec<_>>();
+    unbounded_sites(sites, &directions)
+}
+
+/*
+ Find the sites whose regions are unbounded in any of the given directions, in ascending order.
+ Each direction is paired with the lead a site needs over another along it, as described
+ in is_unbounded_along().
+*/
+fn unbounded_sites(sites: &[Point], directions: &[(&Projection, &dyn Fn(i64) -> i64)]) -> Vec<usize> {
+    (0..sites.len())
+        .filter(|&s| directions.iter().any(|(along, lead)| is_unbounded_along(sites, s, along, lead)))
+        .collect()
+}
+
+/*
+ Check if a site's region is unbounded in the direction of increasing u, where points are
+ given as (u, v) coordinates along and across that direction.
+
+ Far enough along u, a site s must be closer than another site t on the rows (v) where
+ |v - s.v| - |v - t.v| < lead(s.u - t.u). For the manhattan metric the lead is just how far
+ ahead s is, and other metrics scale it or use it to break ties.
+*/
+fn is_unbounded_along(sites: &[Point], s: usize, along: &Projection, lead: &dyn Fn(i64) -> i64) -> bool {
+    let (su, sv) = along(&sites[s]);
+    let (mut first, mut last) = (i64::MIN, i64::MAX); // Range of rows where s is closest
+
+    for (t, site) in sites.iter().enumerate() {
+        if t == s { continue }
+        let (tu, tv) = along(site);
+
+        // s is closer where |v - sv| - |v - tv| < ahead
+        let ahead = lead(su - tu);
+        let apart = (sv - tv).abs();
+        if ahead <= -apart {
+            return false // Dominated
+        } else if ahead > apart {
+            continue // Dominates
+        } else if sv < tv {
+            last = last.min((ahead + sv + tv - 1).div_euclid(2));
+        } else {
+            first = first.max((sv + tv - ahead).div_euclid(2) + 1);
+        }
+    }
+
+    first <= last
+}
+
+/*
+ Find the sites whose regions are infinite under the euclidean metric, in ascending order.
+
+ These are the sites on the boundary of the convex hull of all sites. A site at a corner
+ of the hull owns a cone opening outwards, and one along an edge owns a strip running
+ outwards from the edge; containing every coordinate straight out from the site.
+ Sites within the hull are surrounded, and sites sharing a position with another own nothing.
+*/
+fn convex_hull_sites(sites: &[Point]) -> Vec<usize> {
+    let mut points = sites.to_vec();
+    points.sort_by_key(|p| (p.x, p.y));
+    points.dedup();
+
+    // Find the corners of the hull in clockwise order, with Andrew's monotone chain
+    let cross = |o: &Point, a: &Point, b: &Point| {
+        (a.x as i64 - o.x as i64) * (b.y as i64 - o.y as i64) -
+            (a.y as i64 - o.y as i64) * (b.x as i64 - o.x as i64)
+    };
+    let mut hull: Vec<Point> = Vec::new();
+    for pass in 0..2 {
+        let start = hull.len();
+        let mut chain = points.clone();
+        if pass == 1 { chain.reverse() }
+        for p in chain {
+            while hull.len() >= start + 2 && cross(&hull[hull.len() - 2], &hull[hull.len() - 1], &p) <= 0 {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        hull.pop(); // Starts the other chain
+    }
+
+    // Every site is on the boundary if they all lie along a line
+    let on_boundary = |p: &Point| hull.len() < 3 || (0..hull.len())
+        .any(|i| cross(&hull[i], &hull[(i + 1) % hull.len()], p) == 0);
+    let shared = |p: &Point| sites.iter().filter(|q| *q == p).count() > 1;
+
+    (0..sites.len())
+        .filter(|&i| on_boundary(&sites[i]) && !shared(&sites[i]))
+        .collect()
+}
+
+/*
+ Find the region of every site under any metric by checking the distance from every
+ coordinate in the bounding rectangle to every site.
+
+ Infinite regions are found separately by the metric, and only the coordinates within
+ the bounding rectangle are counted for them.
+*/
+#[allow(dead_code)]
+pub fn voronoi<M: Metric>(sites: &[Point], metric: &M) -> Vec<Region> {
+    let mut regions = vec![Region { area: 0, infinite: false }; sites.len()];
+    let bound = match Rect::bounding(sites) {
+        Some(bound) => bound,
+        None => return regions,
+    };
+
+    for y in bound.from.y..=bound.to.y {
+        for x in bound.from.x..=bound.to.x {
+            if let Some(owner) = closest_site(&Point::new(x, y), sites, metric) {
+                regions[owner].area += 1;
+            }
+        }
+    }
+
+    for site in metric.infinite_sites(sites) {
+        regions[site].infinite = true;
+    }
+    regions
+}
+
+/*
+ Find the index of the site closest to a point under a metric.
+ Returns None if there is a tie which the metric does not break.
+*/
+pub fn closest_site<M: Metric>(point: &Point, sites: &[Point], metric: &M) -> Option<usize> {
+    let mut closest = None;
+    let mut tied = false;
+    for (i, site) in sites.iter().enumerate() {
+        let key = (metric.dist(site, point), metric.tie_break(site, point));
+        match closest {
+            Some((_, closest_key)) if key > closest_key => {},
+            Some((_, closest_key)) if key == closest_key => tied = true,
+            _ => { closest = Some((i, key)); tied = false },
+        }
+    }
+    if tied { None } else { closest.map(|(i, _)| i) }
+}
+
+// Distance Sums //////////////////////////////////////////////////////////////
+
+/*
+ Find the sum of distances from each integer coordinate on a line to every given
+ coordinate, keeping only those no more than max_sum, in ascending order.
+
+ Beyond the outermost coordinates the sum grows by n per step, so only a range of
+ max_sum / n either side needs to be checked. The sum changes by the number of
+ coordinates behind minus the number ahead per step, and is convex; it falls then rises,
+ so the two halves can be merged into order without sorting.
+*/
+fn sorted_distance_sums(coords: &mut [i64], max_sum: i64) -> Vec<i64> {
+    coords.sort();
+    let n = coords.len() as i64;
+    let from = coords[0] - max_sum / n;
+    let to = coords[coords.len() - 1] + max_sum / n;
+
+    // Sweep along the line
+    let mut sums = Vec::new();
+    let mut sum = coords.iter().map(|c| c - from).sum::<i64>();
+    let mut behind = 0; // Coordinates at or behind the current one
+    for x in from..=to {
+        while behind < coords.len() && coords[behind] <= x {
+            behind += 1;
+        }
+        if sum <= max_sum {
+            sums.push(sum);
+        }
+        sum += behind as i64 - (n - behind as i64);
+    }
+
+    // Merge falling and rising halves
+    if sums.is_empty() { return sums }
+    let lowest = (0..sums.len()).min_by_key(|&i| sums[i]).unwrap_or(0);
+    let (mut falling, mut rising) = (lowest as isize, lowest + 1);
+    let mut sorted = Vec::with_capacity(sums.len());
+    while falling >= 0 || rising < sums.len() {
+        if rising == sums.len() || (falling >= 0 && sums[falling as usize] <= sums[rising]) {
+            sorted.push(sums[falling as usize]);
+            falling -= 1;
+        } else {
+            sorted.push(sums[rising]);
+            rising += 1;
+        }
+    }
+    sorted
+}
+
+/*
+ Count the integer coordinates whose manhattan distances to all sites sum to no more than max_sum.
+
+ The manhattan distance separates into independent x and y parts, so the total is the
+ sum of distances along x to every site plus the sum along y. Each is found for every
+ relevant column and row in ascending order, and then pairs of a column and row with
+ a small enough total are counted with two pointers moving in opposite directions.
+*/
+#[allow(dead_code)]
+pub fn manhattan_region_size(sites: &[Point], max_sum: i64) -> usize {
+    if sites.is_empty() || max_sum < 0 { return 0 }
+
+    let x_sums = sorted_distance_sums(&mut sites.iter().map(|p| p.x as i64).collect::<Vec<i64>>(), max_sum);
+    let y_sums = sorted_distance_sums(&mut sites.iter().map(|p| p.y as i64).collect::<Vec<i64>>(), max_sum);
+
+    let mut count = 0;
+    let mut rows = y_sums.len(); // Rows [0, rows) are within range of the current column
+    for x_sum in x_sums {
+        while rows > 0 && x_sum + y_sums[rows - 1] > max_sum {
+            rows -= 1;
+        }
+        count += rows;
+    }
+    count
+}
+
+/*
+ Count the integer coordinates whose distances to all sites sum to no more than max_sum, under any metric.
+
+ The summed distance is convex, so the coordinates within the limit form an unbroken
+ run along each row. The lowest sum along a row is found by binary searching for where
+ the sum stops falling, and each end of the run by binary searching outwards from it.
+ Only rows within reach of the sites' centroid need to be checked.
+*/
+#[allow(dead_code)]
+pub fn region_size<M: Metric>(sites: &[Point], metric: &M, max_sum: i64) -> usize {
+    if sites.is_empty() || max_sum < 0 { return 0 }
+
+    let n = sites.len() as i64;
+    let reach = metric.reach(max_sum / n + 1) + 1; // Allow for rounding
+    let center_x = sites.iter().map(|p| p.x as i64).sum::<i64>().div_euclid(n);
+    let center_y = sites.iter().map(|p| p.y as i64).sum::<i64>().div_euclid(n);
+
+    let mut count = 0;
+    for y in (center_y - reach)..=(center_y + reach) {
+        let sum = |x: i64| sites.iter()
+            .map(|p| metric.dist(p, &Point::new(x as i32, y as i32)))
+            .sum::<i64>();
+
+        // Find lowest sum along row
+        let (mut lo, mut hi) = (center_x - reach, center_x + reach);
+        while lo < hi {
+            let mid = lo + (hi - lo).div_euclid(2);
+            if sum(mid + 1) < sum(mid) { lo = mid + 1 } else { hi = mid }
+        }
+        let lowest = lo;
+        if sum(lowest) > max_sum { continue }
+
+        // Find ends of run within limit
+        let (mut lo, mut hi) = (center_x - reach, lowest);
+        while lo < hi {
+            let mid = lo + (hi - lo).div_euclid(2);
+            if sum(mid) <= max_sum { hi = mid } else { lo = mid + 1 }
+        }
+        let first = lo;
+        let (mut lo, mut hi) = (lowest, center_x + reach);
+        while lo < hi {
+            let mid = lo + (hi - lo + 1).div_euclid(2);
+            if sum(mid) <= max_sum { lo = mid } else { hi = mid - 1 }
+        }
+        count += (lo - first + 1) as usize;
+    }
+    count
+}
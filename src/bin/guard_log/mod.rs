@@ -0,0 +1,515 @@
+
+use super::timestamp::*;
+
+use itertools::Itertools;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+// Event //////////////////////////////////////////////////////////////////////
+
+pub enum Event {
+    ShiftChangeTo(i32),
+    WakeUpAt(usize),
+    SleepAt(usize),
+}
+
+impl Event {
+
+    /*
+     Parse a timestamped event from a line in one of the following formats:
+         [1518-11-01 23:58] Guard #10 begins shift
+         [1518-11-02 00:05] falls asleep
+         [1518-11-02 00:25] wakes up
+
+     Guards may begin their shift at any time, but must only fall asleep
+     or wake up during the midnight hour (00:00 - 00:59).
+    */
+    pub fn parse(line: &str) -> Result<(Timestamp, Event), String> {
+        let split = line.find(']').ok_or_else(|| format!("Missing timestamp: {}", line))?;
+        let timestamp = Timestamp::parse(&line[..=split])?;
+        let words = line[split + 1..].split_whitespace().collect::<Vec<&str>>();
+
+        let event = match words[..] {
+            ["Guard", id, "begins", "shift"] => {
+                let id = id.trim_start_matches('#')
+                    .parse()
+                    .map_err(|_| format!("Invalid guard ID: {}", line))?;
+                Event::ShiftChangeTo(id)
+            },
+            ["wakes", "up"] => Event::WakeUpAt(timestamp.midnight_minute()?),
+            ["falls", "asleep"] => Event::SleepAt(timestamp.midnight_minute()?),
+            _ => return Err(format!("Unknown event: {}", line)),
+        };
+
+        Ok((timestamp, event))
+    }
+}
+
+// Shift //////////////////////////////////////////////////////////////////////
+
+/*
+ A record of when a single guard was asleep during the midnight hour of a shift.
+ Implementations differ only in how they store this information.
+*/
+pub trait Shift {
+
+    /*
+     Begin a new shift where the guard is awake.
+    */
+    fn new(guard: i32, date: Date) -> Self;
+
+    fn guard(&self) -> i32;
+
+    /*
+     Get the date of the midnight hour on duty.
+    */
+    fn date(&self) -> Date;
+
+    /*
+     Check if the guard is asleep at the end of the shift (i.e. after all events so far).
+    */
+    fn is_asleep(&self) -> bool;
+
+    /*
+     Check if the guard is asleep at a particular minute of the midnight hour.
+    */
+    fn is_asleep_at(&self, minute: usize) -> bool;
+
+    /*
+     Record the guard falling asleep or waking up.
+     Events must be recorded in chronological order, and alternate between asleep and awake.
+    */
+    fn record(&mut self, event: Event);
+
+    /*
+     Count the minutes spent asleep during the shift.
+    */
+    fn mins_asleep(&self) -> usize {
+        (0..60).filter(|&m| self.is_asleep_at(m)).count()
+    }
+
+    /*
+     Find the most minutes spent continuously asleep during the shift.
+    */
+    fn longest_nap(&self) -> usize {
+        let mut longest = 0;
+        let mut current = 0;
+        for min in 0..60 {
+            current = if self.is_asleep_at(min) { current + 1 } else { 0 };
+            longest = longest.max(current);
+        }
+        longest
+    }
+}
+
+/*
+ A shift stored as the sequence of events which occurred during it.
+ Questions about the shift are answered by replaying its events.
+*/
+#[allow(dead_code)]
+pub struct EventShift {
+    guard: i32,
+    date: Date,
+    events: Vec<Event>, // Must be ordered and non-overlapping
+}
+
+impl Shift for EventShift {
+
+    fn new(guard: i32, date: Date) -> EventShift {
+        EventShift { guard, date, events: Vec::new() }
+    }
+
+    fn guard(&self) -> i32 {
+        self.guard
+    }
+
+    fn date(&self) -> Date {
+        self.date
+    }
+
+    fn is_asleep(&self) -> bool {
+        matches!(self.events.last(), Some(Event::SleepAt(_)))
+    }
+
+    fn is_asleep_at(&self, min: usize) -> bool {
+        let mut is_asleep = false;
+        for event in self.events.iter() {
+            match event {
+                Event::WakeUpAt(e_min) => {
+                    if min < *e_min { return true } // Asleep before wake event
+                    is_asleep = false;
+                },
+                Event::SleepAt(e_min) => {
+                    if min < *e_min { return false } // Awake before sleep event
+                    is_asleep = true;
+                },
+                Event::ShiftChangeTo(_) => unreachable!(),
+            }
+        }
+
+        // State after final event
+        is_asleep
+    }
+
+    fn record(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    fn mins_asleep(&self) -> usize {
+        let mut mins_asleep = 0;
+        let mut sleep_at = 0;
+        for event in self.events.iter() {
+            match event {
+                Event::WakeUpAt(m) => mins_asleep += m - sleep_at,
+                Event::SleepAt(m) => sleep_at = *m,
+                Event::ShiftChangeTo(_) => unreachable!(),
+            }
+        }
+        if self.is_asleep() { mins_asleep += 60 - sleep_at } // Asleep until end of hour
+        mins_asleep
+    }
+}
+
+/*
+ A shift stored as the state of the guard during each minute of the midnight hour.
+ Each event overwrites the state of all following minutes.
+*/
+#[allow(dead_code)]
+pub struct MinuteShift {
+    guard: i32,
+    date: Date,
+    asleep: [bool; 60],
+}
+
+impl Shift for MinuteShift {
+
+    fn new(guard: i32, date: Date) -> MinuteShift {
+        MinuteShift { guard, date, asleep: [false; 60] }
+    }
+
+    fn guard(&self) -> i32 {
+        self.guard
+    }
+
+    fn date(&self) -> Date {
+        self.date
+    }
+
+    fn is_asleep(&self) -> bool {
+        self.asleep[59]
+    }
+
+    fn is_asleep_at(&self, minute: usize) -> bool {
+        self.asleep[minute]
+    }
+
+    fn record(&mut self, event: Event) {
+        let (minute, asleep) = match event {
+            Event::SleepAt(minute) => (minute, true),
+            Event::WakeUpAt(minute) => (minute, false),
+            Event::ShiftChangeTo(_) => unreachable!(),
+        };
+        for min in &mut self.asleep[minute..] {
+            *min = asleep
+        }
+    }
+}
+
+// GuardLog ///////////////////////////////////////////////////////////////////
+
+/*
+ A log of all guard shifts, using any shift representation for storage.
+*/
+pub struct GuardLog<S: Shift> {
+    shifts: Vec<S>,
+}
+
+impl<S: Shift> GuardLog<S> {
+
+    /*
+     Create a log of shifts from a series of events, one per line.
+
+     The input must adhere to the following restrictions:
+        + Events may be in any order, and will be sorted by timestamp.
+        + Guards may begin their shift at any time, and are on duty for the nearest midnight hour.
+        + Within a shift, the first event (if exists) must be to fall asleep.
+        + All other events within a shift must alternate between asleep and awake,
+          and occur during the midnight hour (00:00 - 00:59).
+    */
+    pub fn parse(lines: &[String]) -> Result<GuardLog<S>, String> {
+        let events = lines.iter()
+            .map(|l| Event::parse(l))
+            .collect::<Result<Vec<(Timestamp, Event)>, String>>()?
+            .into_iter()
+            .sorted_by_key(|(timestamp, _)| *timestamp);
+
+        let mut shifts: Vec<S> = Vec::new();
+        for (timestamp, event) in events {
+
+            // Start of new shift
+            if let Event::ShiftChangeTo(id) = event {
+                shifts.push(S::new(id, timestamp.shift_date()));
+                continue
+            }
+
+            // Guard falls asleep or wakes up
+            let shift = shifts.last_mut()
+                .filter(|s| s.date() == timestamp.date)
+                .ok_or_else(|| format!("Event is not part of any shift: {}", timestamp))?;
+            let falls_asleep = matches!(event, Event::SleepAt(_));
+            if shift.is_asleep() == falls_asleep {
+                let state = if falls_asleep { "asleep" } else { "awake" };
+                return Err(format!("Guard #{} is already {} at {}", shift.guard(), state, timestamp))
+            }
+            shift.record(event);
+        }
+
+        Ok(GuardLog { shifts })
+    }
+
+    #[allow(dead_code)]
+    pub fn shifts(&self) -> &[S] {
+        &self.shifts
+    }
+
+    // Totals /////////////////////////////////////////////
+
+    /*
+     Calculate the total minutes spent asleep by each guard.
+    */
+    pub fn sleep_totals(&self) -> BTreeMap<i32, usize> {
+        let mut sleep_totals = BTreeMap::new();
+        for shift in &self.shifts {
+            *sleep_totals.entry(shift.guard()).or_insert(0) += shift.mins_asleep();
+        }
+        sleep_totals
+    }
+
+    /*
+     Count the number of shifts each guard was asleep for each minute of the midnight hour.
+    */
+    pub fn sleep_histograms(&self) -> BTreeMap<i32, [usize; 60]> {
+        let mut histograms = BTreeMap::new();
+        for shift in &self.shifts {
+            let histogram = histograms.entry(shift.guard()).or_insert([0; 60]);
+            for (min, count) in histogram.iter_mut().enumerate() {
+                if shift.is_asleep_at(min) { *count += 1 }
+            }
+        }
+        histograms
+    }
+
+    /*
+     Count the number of shifts a guard was asleep for each minute of the midnight hour.
+    */
+    pub fn sleep_histogram(&self, guard: i32) -> [usize; 60] {
+        let mut histogram = [0; 60];
+        for shift in self.shifts.iter().filter(|s| s.guard() == guard) {
+            for (min, count) in histogram.iter_mut().enumerate() {
+                if shift.is_asleep_at(min) { *count += 1 }
+            }
+        }
+        histogram
+    }
+
+    /*
+     Summarise the sleeping habits of each guard, ordered by guard ID.
+    */
+    #[allow(dead_code)]
+    pub fn guard_stats(&self) -> Vec<GuardStats> {
+        let mut stats: BTreeMap<i32, GuardStats> = BTreeMap::new();
+        for shift in &self.shifts {
+            let guard = stats.entry(shift.guard()).or_insert(GuardStats {
+                guard: shift.guard(),
+                shifts: 0,
+                mins_asleep: 0,
+                longest_nap: 0,
+                histogram: [0; 60],
+            });
+
+            guard.shifts += 1;
+            guard.mins_asleep += shift.mins_asleep();
+            guard.longest_nap = guard.longest_nap.max(shift.longest_nap());
+            for (min, count) in guard.histogram.iter_mut().enumerate() {
+                if shift.is_asleep_at(min) { *count += 1 }
+            }
+        }
+        stats.into_values().collect()
+    }
+
+    // Strategies /////////////////////////////////////////
+
+    /*
+     Strategy 1: Find the guard asleep for the most minutes,
+     and the minute they were most frequently asleep.
+
+     All tied answers are returned, ordered by guard then minute.
+    */
+    #[allow(dead_code)]
+    pub fn sleepiest_guard(&self) -> Vec<(i32, usize)> {
+        let guards = max_all_by_key(self.sleep_totals(), |(_, total)| *total);
+
+        guards.into_iter()
+            .flat_map(|(guard, _)| {
+                let histogram = self.sleep_histogram(guard);
+                max_all_by_key(0..60, |&min| histogram[min])
+                    .into_iter()
+                    .map(move |min| (guard, min))
+            })
+            .collect()
+    }
+
+    /*
+     Strategy 2: Find the guard most frequently asleep on the same minute, and that minute.
+
+     All tied answers are returned, ordered by guard then minute.
+    */
+    #[allow(dead_code)]
+    pub fn most_frequent_minute(&self) -> Vec<(i32, usize)> {
+        let histograms = self.sleep_histograms();
+        let guard_minutes = histograms.iter()
+            .flat_map(|(&guard, mins)| (0..60).map(move |min| (guard, min, mins[min])));
+
+        max_all_by_key(guard_minutes, |(_, _, count)| *count)
+            .into_iter()
+            .map(|(guard, min, _)| (guard, min))
+            .collect()
+    }
+
+    // Export /////////////////////////////////////////////
+
+    /*
+     Format a report of the sleeping habits of each guard, and the answers to both strategies.
+
+     The probability of each guard being asleep at each minute is drawn as a
+     row of shades from ' ' (never) to '@' (every shift).
+    */
+    #[allow(dead_code)]
+    pub fn report(&self) -> String {
+        const SHADES: &[char] = &[' ', '.', ':', '-', '=', '+', '*', '#', '%', '@'];
+        let stats = self.guard_stats();
+        let mut out = String::new();
+
+        // Summary
+        out += "Guard  | Shifts | Asleep | Longest Nap |       Peak Minute(s) | Peak Probability\n";
+        out += "-------+--------+--------+-------------+----------------------+-----------------\n";
+        for guard in &stats {
+            let peak = max_all_by_key(0..60, |&min| guard.histogram[min]);
+            let peak_mins = if guard.mins_asleep == 0 {
+                String::from("-") // Never asleep
+            } else {
+                peak.iter().map(|m| format!("{:02}", m)).join(",")
+            };
+            out += &format!("#{:<5} | {:>6} | {:>6} | {:>11} | {:>20} | {:>15.1}%\n",
+                            guard.guard, guard.shifts, guard.mins_asleep, guard.longest_nap,
+                            peak_mins, guard.sleep_probability(peak[0]) * 100.0);
+        }
+
+        // Probability of sleeping at each minute
+        out += "\nProbability asleep at 00:00 - 00:59\n";
+        out += &format!("{:7}|{}|\n", "", (0..6).map(|t| format!("{:<10}", t * 10)).join(""));
+        for guard in &stats {
+            let shades = (0..60)
+                .map(|min| {
+                    let shade = (guard.sleep_probability(min) * 9.0).round() as usize;
+                    SHADES[shade]
+                })
+                .collect::<String>();
+            out += &format!("#{:<6}|{}|\n", guard.guard, shades);
+        }
+
+        // Answers
+        let format_answers = |answers: Vec<(i32, usize)>| {
+            let tie = if answers.len() > 1 { " (tie)" } else { "" };
+            let answers = answers.iter().map(|(g, m)| format!("#{} at 00:{:02}", g, m)).join(", ");
+            format!("{}{}", answers, tie)
+        };
+        out += &format!("\nStrategy 1: {}\n", format_answers(self.sleepiest_guard()));
+        out += &format!("Strategy 2: {}\n", format_answers(self.most_frequent_minute()));
+
+        out
+    }
+
+    /*
+     Format a table of the probability of each guard being asleep at each minute,
+     with a row per minute and a column per guard.
+    */
+    #[allow(dead_code)]
+    pub fn probability_table(&self, precision: usize) -> String {
+        let stats = self.guard_stats();
+        let width = stats.iter()
+            .map(|guard| format!("#{}", guard.guard).len())
+            .chain(std::iter::once(precision + 2))
+            .max()
+            .unwrap();
+
+        let mut out = format!("Minute |{}\n", stats.iter()
+            .map(|guard| format!(" {:>w$}", format!("#{}", guard.guard), w = width))
+            .join(""));
+        out += &format!("-------+{}\n", "-".repeat(stats.len() * (width + 1)));
+        for min in 0..60 {
+            out += &format!("00:{:02}  |{}\n", min, stats.iter()
+                .map(|guard| format!(" {:>w$.p$}", guard.sleep_probability(min), w = width, p = precision))
+                .join(""));
+        }
+        out
+    }
+
+    /*
+     Format a guard×minute matrix as CSV.
+     Each row contains a guard ID, their number of shifts, and the number of
+     those shifts they were asleep during each minute of the midnight hour.
+    */
+    #[allow(dead_code)]
+    pub fn to_csv(&self) -> String {
+        let mut csv = format!("guard,shifts,{}\n", (0..60).join(","));
+        for guard in self.guard_stats() {
+            csv += &format!("{},{},{}\n", guard.guard, guard.shifts, guard.histogram.iter().join(","));
+        }
+        csv
+    }
+}
+
+// GuardStats /////////////////////////////////////////////////////////////////
+
+/*
+ A summary of the sleeping habits of a single guard across all of their shifts.
+*/
+pub struct GuardStats {
+    pub guard: i32,
+    pub shifts: usize,
+    pub mins_asleep: usize,
+    pub longest_nap: usize,       // Longest continuous sleep during any single shift
+    pub histogram: [usize; 60],   // Number of shifts asleep during each minute
+}
+
+impl GuardStats {
+
+    /*
+     Get the probability of the guard being asleep at a minute of any one shift.
+    */
+    pub fn sleep_probability(&self, minute: usize) -> f64 {
+        self.histogram[minute] as f64 / self.shifts as f64
+    }
+}
+
+// Functions //////////////////////////////////////////////////////////////////
+
+/*
+ Find every item sharing the largest key, in their original order.
+*/
+fn max_all_by_key<T, K, I, F>(items: I, f: F) -> Vec<T>
+    where I: IntoIterator<Item=T>,
+          K: Ord,
+          F: Fn(&T) -> K
+{
+    let mut best: Vec<T> = Vec::new();
+    for item in items {
+        match best.first().map(|b| f(&item).cmp(&f(b))) {
+            Some(Ordering::Less) => (),
+            Some(Ordering::Equal) => best.push(item),
+            _ => best = vec![item],
+        }
+    }
+    best
+}
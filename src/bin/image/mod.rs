@@ -0,0 +1,158 @@
+
+use std::io::Write;
+
+// Colour /////////////////////////////////////////////////////////////////////
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+
+    pub const BLACK: Colour = Colour { r: 0, g: 0, b: 0 };
+    #[allow(dead_code)]
+    pub const WHITE: Colour = Colour { r: 255, g: 255, b: 255 };
+
+    pub fn new(r: u8, g: u8, b: u8) -> Colour {
+        Colour { r, g, b }
+    }
+
+    /*
+     Linearly interpolate between two colours, where t is in the range 0..=1.
+    */
+    #[allow(dead_code)]
+    pub fn lerp(&self, other: Colour, t: f64) -> Colour {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Colour::new(mix(self.r, other.r), mix(self.g, other.g), mix(self.b, other.b))
+    }
+}
+
+// Image //////////////////////////////////////////////////////////////////////
+
+/*
+ A simple RGB raster image.
+ Can be written to disk as a PPM file or previewed in a true-colour terminal.
+*/
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Colour>,
+}
+
+impl Image {
+
+    /*
+     Create an image filled with a single colour.
+    */
+    pub fn new(width: usize, height: usize, fill: Colour) -> Image {
+        Image { width, height, pixels: vec![fill; width * height] }
+    }
+
+    #[allow(dead_code)]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[allow(dead_code)]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    #[allow(dead_code)]
+    pub fn get(&self, x: usize, y: usize) -> Colour {
+        self.pixels[y * self.width + x]
+    }
+
+    /*
+     Set the colour of a pixel.
+     Pixels outside the image are ignored.
+    */
+    pub fn set(&mut self, x: usize, y: usize, colour: Colour) {
+        if x < self.width && y < self.height {
+            self.pixels[y * self.width + x] = colour;
+        }
+    }
+
+    /*
+     Draw the one pixel wide outline of a rectangle with its top-left corner at (x,y).
+    */
+    #[allow(dead_code)]
+    pub fn outline(&mut self, x: usize, y: usize, width: usize, height: usize, colour: Colour) {
+        if width == 0 || height == 0 { return }
+        for i in x..(x + width) {
+            self.set(i, y, colour);
+            self.set(i, y + height - 1, colour);
+        }
+        for j in y..(y + height) {
+            self.set(x, j, colour);
+            self.set(x + width - 1, j, colour);
+        }
+    }
+
+    // Export /////////////////////////////////////////////
+
+    /*
+     Write the image as a binary PPM (P6) file.
+    */
+    #[allow(dead_code)]
+    pub fn write_ppm(&self, path: &str) -> std::io::Result<()> {
+        let mut file = std::io::BufWriter::new(std::fs::File::create(path)?);
+        write!(file, "P6\n{} {}\n255\n", self.width, self.height)?;
+        for pixel in &self.pixels {
+            file.write_all(&[pixel.r, pixel.g, pixel.b])?;
+        }
+        file.flush()
+    }
+
+    /*
+     Render the image for a true-colour terminal using ANSI escape codes.
+
+     Each character cell shows two pixels stacked vertically using a half block.
+     Images wider than max_columns are scaled down by averaging square blocks of pixels.
+    */
+    #[allow(dead_code)]
+    pub fn to_ansi(&self, max_columns: usize) -> String {
+        let max_columns = max_columns.max(1);
+        let scale = self.width.div_ceil(max_columns).max(1);
+        let columns = self.width.div_ceil(scale);
+        let rows = self.height.div_ceil(scale);
+
+        let mut out = String::new();
+        for row in (0..rows).step_by(2) {
+            for column in 0..columns {
+                let top = self.average(column * scale, row * scale, scale);
+                let bottom = if row + 1 < rows {
+                    self.average(column * scale, (row + 1) * scale, scale)
+                } else {
+                    Colour::BLACK
+                };
+                out += &format!("\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m\u{2580}",
+                                top.r, top.g, top.b, bottom.r, bottom.g, bottom.b);
+            }
+            out += "\x1b[0m\n";
+        }
+        out
+    }
+
+    /*
+     Find the average colour of a square block of pixels, clipped to the image.
+    */
+    fn average(&self, x: usize, y: usize, size: usize) -> Colour {
+        let (mut r, mut g, mut b, mut n) = (0, 0, 0, 0);
+        for j in y..(y + size).min(self.height) {
+            for i in x..(x + size).min(self.width) {
+                let pixel = self.get(i, j);
+                r += pixel.r as usize;
+                g += pixel.g as usize;
+                b += pixel.b as usize;
+                n += 1;
+            }
+        }
+        if n == 0 { return Colour::BLACK }
+        Colour::new((r / n) as u8, (g / n) as u8, (b / n) as u8)
+    }
+}
@@ -0,0 +1,566 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+
+// Errors /////////////////////////////////////////////////////////////////////
+
+/*
+ A reason a tree can't be parsed.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseErrorKind {
+    Truncated(&'static str), // The input ended while expecting part of a node
+    TrailingInput(String),   // The input continues after the root node
+    InvalidNumber(String),   // A word which isn't a whole number
+    Overflow(String),        // A number too large to be counted
+    Io(String),              // The input couldn't be read
+}
+
+/*
+ A reason a tree can't be parsed, the offset (in bytes) of the input where it was found, and
+ the path to the node being read, as the index of each child node from the root.
+*/
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub offset: usize,
+    pub path: Vec<usize>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.kind {
+            ParseErrorKind::Truncated(expecting) => write!(f, "Unexpected end of input, expecting {}", expecting)?,
+            ParseErrorKind::TrailingInput(word) => write!(f, "Unexpected input after the root node: {}", word)?,
+            ParseErrorKind::InvalidNumber(word) => write!(f, "Invalid number: {}", word)?,
+            ParseErrorKind::Overflow(word) => write!(f, "Number too large: {}", word)?,
+            ParseErrorKind::Io(error) => write!(f, "Failed to read input: {}", error)?,
+        }
+        write!(f, " at byte {}", self.offset)?;
+
+        // Child nodes are numbered from 1, as referred to by metadata entries
+        if let ParseErrorKind::TrailingInput(_) = self.kind {
+            return Ok(())
+        }
+        write!(f, ", in node root")?;
+        for i in &self.path {
+            write!(f, " -> {}", i + 1)?;
+        }
+        Ok(())
+    }
+}
+
+// LicenseTree ////////////////////////////////////////////////////////////////
+
+/*
+ A node of a license tree, with any number of child nodes and metadata entries.
+
+ A tree is stored as a stream of numbers, where each node is a header of the number of
+ child nodes and metadata entries, followed by its child nodes, then its metadata entries.
+ e.g.
+     2 3 0 3 10 11 12 1 1 0 1 99 2 1 1 2
+     A----------------------------------
+         B----------- C-----------
+                          D-----
+
+ Nothing recurses once per level of nesting (including dropping, cloning, and comparing),
+ so trees of any depth can be used without overflowing the stack.
+*/
+pub struct LicenseTree {
+    pub children: Vec<LicenseTree>,
+    pub metadata: Vec<usize>,
+}
+
+impl LicenseTree {
+
+    /*
+     Parse a tree from a string of whitespace separated numbers.
+    */
+    pub fn parse(input: &str) -> Result<LicenseTree, ParseError> {
+        LicenseTree::from_reader(input.as_bytes())
+    }
+
+    /*
+     Parse a tree from a stream of whitespace separated numbers, read as needed rather than
+     all at once. The input must contain exactly one tree.
+    */
+    pub fn from_reader<R: Read>(reader: R) -> Result<LicenseTree, ParseError> {
+        let mut words = Words::new(BufReader::new(reader));
+        let tree = LicenseTree::read(&mut words)?;
+
+        let error = |kind, offset| ParseError { kind, offset, path: Vec::new() };
+        match words.next() {
+            Ok(None) => Ok(tree),
+            Ok(Some(word)) => Err(error(ParseErrorKind::TrailingInput(words.text(word.offset)), word.offset)),
+            Err(e) => Err(error(ParseErrorKind::Io(e.to_string()), words.offset)),
+        }
+    }
+
+    /*
+     Read a tree from a stream of words.
+
+     Nodes whose child nodes are still being read are kept on a stack, along with their header
+     and the child nodes read so far. Once every child node has been read, the metadata entries
+     are read and the complete node is added to its parent.
+    */
+    fn read<R: BufRead>(words: &mut Words<R>) -> Result<LicenseTree, ParseError> {
+        let mut stack: Vec<(usize, usize, Vec<LicenseTree>)> = Vec::new(); // Number of child nodes and metadata entries, and child nodes
+        let mut path = Vec::new(); // Path to the node being read
+        loop {
+            let num_children = words.expect("child node count", &path)?;
+            let num_metadata = words.expect("metadata entry count", &path)?;
+            stack.push((num_children, num_metadata, Vec::new()));
+
+            // Complete nodes until one needs another child node
+            loop {
+                let (num_children, _, children) = stack.last().unwrap();
+                if children.len() < *num_children {
+                    path.push(children.len());
+                    break
+                }
+
+                let (_, num_metadata, children) = stack.pop().unwrap();
+                let mut metadata = Vec::new();
+                for _ in 0..num_metadata {
+                    metadata.push(words.expect("metadata entry", &path)?);
+                }
+
+                let node = LicenseTree { children, metadata };
+                path.pop();
+                match stack.last_mut() {
+                    Some((_, _, siblings)) => siblings.push(node),
+                    None => return Ok(node),
+                }
+            }
+        }
+    }
+
+    /*
+     Same as parse(), except recursing for each child node.
+     Kept for comparison, as this overflows the stack for deeply nested trees.
+    */
+    #[allow(dead_code)]
+    pub fn parse_recursive(input: &str) -> Result<LicenseTree, String> {
+        let mut numbers = input.split_whitespace()
+            .map(|x| x.parse::<usize>().map_err(|_| format!("Invalid number: {}", x)));
+
+        let tree = LicenseTree::read_recursive(&mut numbers)?;
+        if numbers.next().is_some() {
+            return Err(String::from("Unexpected numbers after the root node"))
+        }
+        Ok(tree)
+    }
+
+    fn read_recursive<I>(numbers: &mut I) -> Result<LicenseTree, String>
+        where I: Iterator<Item=Result<usize, String>>
+    {
+        let mut next = || numbers.next().unwrap_or_else(|| Err(String::from("Unexpected end of input")));
+        let num_children = next()?;
+        let num_metadata = next()?;
+
+        let mut children = Vec::new();
+        for _ in 0..num_children {
+            children.push(LicenseTree::read_recursive(numbers)?);
+        }
+        let mut metadata = Vec::new();
+        for _ in 0..num_metadata {
+            metadata.push(numbers.next().unwrap_or_else(|| Err(String::from("Unexpected end of input")))?);
+        }
+
+        Ok(LicenseTree { children, metadata })
+    }
+
+    /*
+     Get the tree as a stream of numbers, in the same format it's parsed from.
+    */
+    pub fn to_numbers(&self) -> Vec<usize> {
+        let mut numbers = vec![self.children.len(), self.metadata.len()];
+
+        // Path to the current node, and the next child of each to write
+        let mut stack = vec![(self, 0)];
+        while let Some((node, next_child)) = stack.last_mut() {
+            let node = *node;
+            match node.children.get(*next_child) {
+                Some(child) => {
+                    *next_child += 1;
+                    numbers.push(child.children.len());
+                    numbers.push(child.metadata.len());
+                    stack.push((child, 0));
+                },
+                None => {
+                    numbers.extend(&node.metadata);
+                    stack.pop();
+                }
+            }
+        }
+        numbers
+    }
+
+    // Puzzle /////////////////////////////////////////////
+
+    /*
+     Find the sum of the metadata entries of every node.
+    */
+    pub fn metadata_sum(&self) -> usize {
+        self.pre_order()
+            .map(|node| node.metadata.iter().sum::<usize>())
+            .sum()
+    }
+
+    /*
+     Find the value of the node. Without child nodes, this is the sum of its metadata entries.
+     Otherwise, each metadata entry is a (1-based) index of a child node whose value is added,
+     where entries which aren't a valid index are skipped.
+    */
+    #[allow(dead_code)]
+    pub fn value(&self) -> usize {
+
+        // Nodes are visited after their children, whose values are on top of the stack
+        let mut values: Vec<usize> = Vec::new();
+        for node in self.post_order() {
+            let children = values.split_off(values.len() - node.children.len());
+            values.push(node_value(&node.metadata, &children));
+        }
+        values[0]
+    }
+
+    // Traversal //////////////////////////////////////////
+
+    /*
+     Iterate over every node, each before its children.
+    */
+    pub fn pre_order(&self) -> PreOrder<'_> {
+        PreOrder { stack: vec![self] }
+    }
+
+    /*
+     Iterate over every node, each after its children.
+    */
+    #[allow(dead_code)]
+    pub fn post_order(&self) -> PostOrder<'_> {
+        PostOrder { stack: vec![(self, 0)] }
+    }
+
+    /*
+     Iterate over every node with its depth (where the root is 0), in order of depth.
+    */
+    #[allow(dead_code)]
+    pub fn by_depth(&self) -> ByDepth<'_> {
+        ByDepth { queue: vec![(0, self)].into_iter().collect() }
+    }
+}
+
+impl Clone for LicenseTree {
+    fn clone(&self) -> LicenseTree {
+
+        // Path to the current node, and the clones of its child nodes so far
+        let mut stack = vec![(self, Vec::new())];
+        loop {
+            let (node, children) = stack.last().unwrap();
+            if let Some(child) = node.children.get(children.len()) {
+                stack.push((child, Vec::new()));
+                continue
+            }
+
+            let (node, children) = stack.pop().unwrap();
+            let clone = LicenseTree { children, metadata: node.metadata.clone() };
+            match stack.last_mut() {
+                Some((_, siblings)) => siblings.push(clone),
+                None => return clone,
+            }
+        }
+    }
+}
+
+impl PartialEq for LicenseTree {
+    fn eq(&self, other: &LicenseTree) -> bool {
+        self.to_numbers() == other.to_numbers()
+    }
+}
+
+impl Drop for LicenseTree {
+    fn drop(&mut self) {
+        // Detach descendants so that each is dropped without any children
+        let mut descendants = std::mem::take(&mut self.children);
+        while let Some(mut node) = descendants.pop() {
+            descendants.append(&mut node.children);
+        }
+    }
+}
+
+impl std::fmt::Debug for LicenseTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "LicenseTree({})", self)
+    }
+}
+
+impl Display for LicenseTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let numbers = self.to_numbers().iter()
+            .map(|n| n.to_string())
+            .collect::<Vec<String>>();
+        write!(f, "{}", numbers.join(" "))
+    }
+}
+
+// Words //////////////////////////////////////////////////////////////////////
+
+/*
+ A whitespace separated word, starting at an offset (in bytes) of the input.
+*/
+struct Word {
+    offset: usize,
+    number: Result<usize, ParseErrorKind>,
+}
+
+/*
+ Reads words from a stream a buffer at a time, converting them to numbers as they're read.
+*/
+struct Words<R> {
+    reader: R,
+    offset: usize,
+    text: Vec<u8>, // Start of the last word, only used to report errors
+}
+
+impl<R: BufRead> Words<R> {
+    const MAX_TEXT: usize = 24;
+
+    fn new(reader: R) -> Words<R> {
+        Words { reader, offset: 0, text: Vec::new() }
+    }
+
+    /*
+     Consume bytes while they match a condition, passing each to a function.
+     Returns whether there's more input.
+    */
+    fn consume_while<C, F>(&mut self, condition: C, mut f: F) -> std::io::Result<bool>
+        where C: Fn(u8) -> bool,
+              F: FnMut(u8)
+    {
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                return Ok(false)
+            }
+            let matched = buffer.iter().take_while(|&&b| condition(b)).count();
+            buffer[..matched].iter().for_each(|&b| f(b));
+
+            let more = matched < buffer.len();
+            self.reader.consume(matched);
+            self.offset += matched;
+            if more {
+                return Ok(true)
+            }
+        }
+    }
+
+    /*
+     Read the next word, if any.
+    */
+    fn next(&mut self) -> std::io::Result<Option<Word>> {
+        self.consume_while(|b| b.is_ascii_whitespace(), |_| ())?;
+
+        let offset = self.offset;
+        let mut text = std::mem::take(&mut self.text);
+        text.clear();
+        let mut number = Some(0usize); // None once overflowed
+        let mut is_number = true;
+        self.consume_while(|b| !b.is_ascii_whitespace(), |b| {
+            if text.len() < Words::<R>::MAX_TEXT { text.push(b) }
+            is_number &= b.is_ascii_digit();
+            if is_number {
+                number = number.and_then(|n| n.checked_mul(10)).and_then(|n| n.checked_add((b - b'0') as usize));
+            }
+        })?;
+        self.text = text;
+        if self.offset == offset {
+            return Ok(None) // End of input
+        }
+
+        let number = match number {
+            _ if !is_number => Err(ParseErrorKind::InvalidNumber(self.text(offset))),
+            Some(number) => Ok(number),
+            None => Err(ParseErrorKind::Overflow(self.text(offset))),
+        };
+        Ok(Some(Word { offset, number }))
+    }
+
+    /*
+     Get the text of the last word read, which started at an offset.
+     Long words are shortened.
+    */
+    fn text(&self, offset: usize) -> String {
+        let mut text = String::from_utf8_lossy(&self.text).into_owned();
+        if self.offset - offset > Words::<R>::MAX_TEXT { text += "..." }
+        text
+    }
+
+    /*
+     Read a number which must be next while reading the node at the end of a path.
+    */
+    fn expect(&mut self, expecting: &'static str, path: &[usize]) -> Result<usize, ParseError> {
+        let error = |kind, offset| ParseError { kind, offset, path: path.to_vec() };
+        match self.next() {
+            Ok(Some(Word { offset, number, .. })) => number.map_err(|kind| error(kind, offset)),
+            Ok(None) => Err(error(ParseErrorKind::Truncated(expecting), self.offset)),
+            Err(e) => Err(error(ParseErrorKind::Io(e.to_string()), self.offset)),
+        }
+    }
+}
+
+// Evaluation ///////////////////////////////////////////////////////////////////
+
+/*
+ The number of numbers which make up a node, the sum of its and its descendants' metadata
+ entries, and its value.
+*/
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NodeInfo {
+    pub size: usize,
+    pub metadata_sum: usize,
+    pub value: usize,
+}
+
+/*
+ Find the value of a node from its metadata entries, and the values of its child nodes.
+*/
+fn node_value(metadata: &[usize], children: &[usize]) -> usize {
+    if children.is_empty() {
+        metadata.iter().sum()
+    } else {
+        metadata.iter()
+            .filter_map(|&m| m.checked_sub(1).and_then(|i| children.get(i)))
+            .sum()
+    }
+}
+
+/*
+ Evaluate the node at the start of a stream of numbers, without building a tree.
+
+ As in LicenseTree::read(), nodes whose child nodes are still being evaluated are kept on a
+ stack, with the values of the child nodes evaluated so far and their metadata sum.
+*/
+#[allow(dead_code)]
+pub fn evaluate(numbers: &[usize]) -> Result<NodeInfo, String> {
+    let truncated = || String::from("Unexpected end of input");
+    let header = |pos: usize| match numbers.get(pos..pos + 2) {
+        Some(header) => Ok((header[0], header[1])),
+        None => Err(truncated()),
+    };
+
+    // Start of node, number of child nodes and metadata entries, values of child nodes, and metadata sum
+    let (num_children, num_metadata) = header(0)?;
+    let mut stack = vec![(0, num_children, num_metadata, Vec::new(), 0)];
+    let mut pos = 2;
+    loop {
+        let (_, num_children, _, children, _) = stack.last_mut().unwrap();
+        if children.len() < *num_children {
+            let (num_children, num_metadata) = header(pos)?;
+            stack.push((pos, num_children, num_metadata, Vec::new(), 0));
+            pos += 2;
+            continue
+        }
+
+        let (start, _, num_metadata, children, descendants_sum) = stack.pop().unwrap();
+        let end = pos.checked_add(num_metadata).ok_or_else(truncated)?;
+        let metadata = numbers.get(pos..end).ok_or_else(truncated)?;
+        pos = end;
+
+        let metadata_sum = descendants_sum + metadata.iter().sum::<usize>();
+        let value = node_value(metadata, &children);
+        match stack.last_mut() {
+            Some((_, _, _, siblings, sum)) => {
+                siblings.push(value);
+                *sum += metadata_sum;
+            },
+            None => return Ok(NodeInfo { size: pos - start, metadata_sum, value }),
+        }
+    }
+}
+
+/*
+ Same as evaluate(), except recursing for each child node.
+ Kept for comparison, as this overflows the stack for deeply nested trees.
+*/
+#[allow(dead_code)]
+pub fn evaluate_recursive(numbers: &[usize]) -> Result<NodeInfo, String> {
+    let truncated = || String::from("Unexpected end of input");
+    let num_children = *numbers.first().ok_or_else(truncated)?;
+    let num_metadata = *numbers.get(1).ok_or_else(truncated)?;
+    let mut size = 2; // Header
+
+    // Process child nodes
+    let mut metadata_sum = 0;
+    let mut children = Vec::new();
+    for _ in 0..num_children {
+        let child = evaluate_recursive(&numbers[size..])?;
+        size += child.size;
+        metadata_sum += child.metadata_sum;
+        children.push(child.value);
+    }
+
+    // Process metadata
+    let end = size.checked_add(num_metadata).ok_or_else(truncated)?;
+    let metadata = numbers.get(size..end).ok_or_else(truncated)?;
+    metadata_sum += metadata.iter().sum::<usize>();
+    let value = node_value(metadata, &children);
+
+    Ok(NodeInfo { size: end, metadata_sum, value })
+}
+
+// Iterators //////////////////////////////////////////////////////////////////
+
+pub struct PreOrder<'a> {
+    stack: Vec<&'a LicenseTree>, // Nodes yet to be visited, with the next on top
+}
+
+impl<'a> Iterator for PreOrder<'a> {
+    type Item = &'a LicenseTree;
+
+    fn next(&mut self) -> Option<&'a LicenseTree> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.children.iter().rev());
+        Some(node)
+    }
+}
+
+pub struct PostOrder<'a> {
+    stack: Vec<(&'a LicenseTree, usize)>, // Path to the current node, and the next child of each to visit
+}
+
+impl<'a> Iterator for PostOrder<'a> {
+    type Item = &'a LicenseTree;
+
+    fn next(&mut self) -> Option<&'a LicenseTree> {
+        loop {
+            let (node, next_child) = self.stack.last_mut()?;
+            let node = *node;
+            match node.children.get(*next_child) {
+                Some(child) => {
+                    *next_child += 1;
+                    self.stack.push((child, 0));
+                },
+                None => {
+                    self.stack.pop();
+                    return Some(node)
+                }
+            }
+        }
+    }
+}
+
+pub struct ByDepth<'a> {
+    queue: VecDeque<(usize, &'a LicenseTree)>,
+}
+
+impl<'a> Iterator for ByDepth<'a> {
+    type Item = (usize, &'a LicenseTree);
+
+    fn next(&mut self) -> Option<(usize, &'a LicenseTree)> {
+        let (depth, node) = self.queue.pop_front()?;
+        self.queue.extend(node.children.iter().map(|child| (depth + 1, child)));
+        Some((depth, node))
+    }
+}
@@ -0,0 +1,620 @@
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+
+// Reaction Rules /////////////////////////////////////////////////////////////
+
+/*
+ The result of two adjacent units meeting.
+*/
+#[allow(dead_code)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Reaction<U> {
+    Inert,      // Both units remain
+    Annihilate, // Both units are destroyed
+    Product(U), // Both units are replaced by a single new unit
+}
+
+/*
+ Determines how adjacent units in a polymer react with each other.
+*/
+pub trait ReactionRule<U> {
+    fn react(&self, left: U, right: U) -> Reaction<U>;
+}
+
+/*
+ Units of the same type and opposite polarity annihilate each other.
+ The type of a unit is its letter, and its polarity is its case. e.g. 'a' and 'A'
+*/
+pub struct OppositePolarity;
+
+impl ReactionRule<char> for OppositePolarity {
+    fn react(&self, left: char, right: char) -> Reaction<char> {
+        if (left as i32 - right as i32).abs() == 32 {
+            Reaction::Annihilate
+        } else {
+            Reaction::Inert
+        }
+    }
+}
+
+impl ReactionRule<u8> for OppositePolarity {
+    fn react(&self, left: u8, right: u8) -> Reaction<u8> {
+        if left ^ right == 32 && left.is_ascii_alphabetic() {
+            Reaction::Annihilate
+        } else {
+            Reaction::Inert
+        }
+    }
+}
+
+/*
+ An explicit set of unit pairs which annihilate each other in either order.
+*/
+pub struct AnnihilationPairs<U> {
+    pairs: HashSet<(U, U)>,
+}
+
+impl<U: Copy + Eq + Hash> AnnihilationPairs<U> {
+    #[allow(dead_code)]
+    pub fn new(pairs: &[(U, U)]) -> AnnihilationPairs<U> {
+        AnnihilationPairs {
+            pairs: pairs.iter()
+                .flat_map(|&(a, b)| vec![(a, b), (b, a)])
+                .collect()
+        }
+    }
+}
+
+impl<U: Copy + Eq + Hash> ReactionRule<U> for AnnihilationPairs<U> {
+    fn react(&self, left: U, right: U) -> Reaction<U> {
+        if self.pairs.contains(&(left, right)) { Reaction::Annihilate } else { Reaction::Inert }
+    }
+}
+
+/*
+ A table of ordered unit pairs and the reaction between them.
+ Pairs not in the table are inert.
+*/
+pub struct RewriteTable<U> {
+    rules: HashMap<(U, U), Reaction<U>>,
+}
+
+impl<U: Copy + Eq + Hash> RewriteTable<U> {
+
+    #[allow(dead_code)]
+    pub fn new() -> RewriteTable<U> {
+        RewriteTable { rules: HashMap::new() }
+    }
+
+    /*
+     Add a rule for when left meets right (in that order).
+    */
+    #[allow(dead_code)]
+    pub fn with(mut self, left: U, right: U, reaction: Reaction<U>) -> RewriteTable<U> {
+        self.rules.insert((left, right), reaction);
+        self
+    }
+}
+
+impl<U: Copy + Eq + Hash> ReactionRule<U> for RewriteTable<U> {
+    fn react(&self, left: U, right: U) -> Reaction<U> {
+        self.rules.get(&(left, right)).cloned().unwrap_or(Reaction::Inert)
+    }
+}
+
+// Reactor ////////////////////////////////////////////////////////////////////
+
+/*
+ Processes polymer reactions as units are fed in one at a time.
+
+ Stack-based solution for efficient removal adapted from:
+ https://www.reddit.com/r/adventofcode/comments/a3912m/2018_day_5_solutions/
+
+ When two units react together, one will be the unit being added, while
+ the other will be on the top of the stack. This allows us to efficiently discard them
+ with minimal operations compared to a vector (requires re-shuffling; O(n) removals) or
+ a double-linked list / sparse array (O(1) removals, but with additional overhead).
+
+ A product replaces the top of the stack, and must then be checked against the
+ new top of the stack before the next unit. Since every reaction shortens the
+ polymer, this still only requires a single pass; always reacting the leftmost
+ reactive pair first. When each unit annihilates with exactly one opposite unit
+ (e.g. OppositePolarity) this is the only possible result, but for other rules
+ the result may depend on that order.
+
+ Only the stack of units which have not (yet) reacted is stored, so a polymer
+ may be streamed through the reactor without ever being held in memory.
+*/
+pub struct Reactor<'r, U, R> {
+    rule: &'r R,
+    stack: Vec<U>,
+}
+
+impl<'r, U: Copy, R: ReactionRule<U>> Reactor<'r, U, R> {
+
+    pub fn new(rule: &'r R) -> Reactor<'r, U, R> {
+        Reactor { rule, stack: Vec::new() }
+    }
+
+    /*
+     Add a unit to the right end of the polymer, and process any resulting reactions.
+    */
+    pub fn push(&mut self, unit: U) {
+        let mut right = unit;
+        loop {
+            let left = match self.stack.last() {
+                Some(&left) => left,
+                None => { self.stack.push(right); return }
+            };
+
+            match self.rule.react(left, right) {
+                Reaction::Inert => {
+                    // Keep both units (at least for now)
+                    self.stack.push(right);
+                    return
+                },
+                Reaction::Annihilate => {
+                    // Discard both units
+                    self.stack.pop();
+                    return
+                },
+                Reaction::Product(product) => {
+                    // Replace both units, then check if the product reacts with its new left
+                    self.stack.pop();
+                    right = product;
+                }
+            }
+        }
+    }
+
+    /*
+     Get the length of the polymer reacted so far.
+    */
+    pub fn len(&self) -> usize {
+        self.stack.len()
+    }
+
+    /*
+     Get the polymer reacted so far.
+    */
+    pub fn into_polymer(self) -> Vec<U> {
+        self.stack
+    }
+}
+
+/*
+ Process polymer reactions until inert.
+*/
+#[allow(dead_code)]
+pub fn react<U, I, R>(polymer: I, rule: &R) -> Vec<U>
+    where U: Copy,
+          I: IntoIterator<Item=U>,
+          R: ReactionRule<U>
+{
+    let mut reactor = Reactor::new(rule);
+    for unit in polymer {
+        reactor.push(unit);
+    }
+    reactor.into_polymer()
+}
+
+// In Place ///////////////////////////////////////////////////////////////////
+
+/*
+ Add a unit to the right end of a reacted polymer stored in buffer[..len],
+ and process any resulting reactions.
+
+ Returns the new length of the reacted polymer, and whether the unit was inert
+ with its left neighbour (i.e. was kept without reacting).
+*/
+fn push_in_place<R: ReactionRule<u8>>(buffer: &mut [u8], len: usize, unit: u8, rule: &R) -> (usize, bool) {
+    let mut len = len;
+    let mut right = unit;
+    let mut reacted = false;
+    loop {
+        if len == 0 {
+            buffer[0] = right;
+            return (1, !reacted)
+        }
+
+        match rule.react(buffer[len - 1], right) {
+            Reaction::Inert => {
+                buffer[len] = right;
+                return (len + 1, !reacted)
+            },
+            Reaction::Annihilate => {
+                return (len - 1, false)
+            },
+            Reaction::Product(product) => {
+                len -= 1;
+                right = product;
+                reacted = true;
+            }
+        }
+    }
+}
+
+/*
+ Process polymer reactions until inert, without any additional memory.
+
+ This is the same stack-based algorithm as the Reactor, except the stack is the
+ reacted prefix of the polymer itself. Since the stack can never grow faster than
+ units are read, each unit can be moved into the prefix before its old position is needed.
+
+ Returns the length of the reacted polymer, which is stored in polymer[..len].
+*/
+#[allow(dead_code)]
+pub fn react_in_place<R: ReactionRule<u8>>(polymer: &mut [u8], rule: &R) -> usize {
+    let mut len = 0;
+    for i in 0..polymer.len() {
+        let unit = polymer[i];
+        len = push_in_place(polymer, len, unit, rule).0;
+    }
+    len
+}
+
+/*
+ Join two adjacent reacted polymers stored in buffer[..left_len] and
+ buffer[right_start..right_start + right_len], processing any reactions at the boundary.
+
+ Only units near the boundary can react, since each polymer is already inert.
+ Once a unit from the right polymer is kept without reacting, the remaining units
+ are inert with their original left neighbour, and can be moved across in bulk.
+
+ Returns the length of the joined polymer, which is stored in buffer[..len].
+*/
+fn merge_in_place<R: ReactionRule<u8>>(buffer: &mut [u8], left_len: usize,
+                                      right_start: usize, right_len: usize, rule: &R) -> usize {
+    let mut len = left_len;
+    for i in right_start..(right_start + right_len) {
+        let (new_len, inert) = push_in_place(buffer, len, buffer[i], rule);
+        len = new_len;
+        if inert {
+            // Move remainder without reacting
+            let remainder = (i + 1)..(right_start + right_len);
+            let remainder_len = remainder.len();
+            buffer.copy_within(remainder, len);
+            return len + remainder_len
+        }
+    }
+    len
+}
+
+/*
+ Process polymer reactions until inert using multiple threads.
+
+ The polymer is split into one chunk per thread, each of which is reacted in place
+ independently. The reacted chunks are then merged from left to right, where
+ only units at each boundary need to be considered.
+
+ Reacting chunks independently changes the order of reactions, so the result only
+ matches react_in_place for rules where that order doesn't matter (e.g. OppositePolarity).
+
+ Returns the length of the reacted polymer, which is stored in polymer[..len].
+*/
+#[allow(dead_code)]
+pub fn react_in_place_parallel<R>(polymer: &mut [u8], rule: &R, threads: usize) -> usize
+    where R: ReactionRule<u8> + Sync
+{
+    if polymer.is_empty() {
+        return 0
+    }
+    let threads = threads.clamp(1, polymer.len());
+    let chunk_size = polymer.len().div_ceil(threads);
+
+    // React each chunk independently
+    let chunk_lens = std::thread::scope(|scope| {
+        polymer.chunks_mut(chunk_size)
+            .map(|chunk| scope.spawn(move || react_in_place(chunk, rule)))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<usize>>()
+    });
+
+    // Merge reacted chunks
+    let mut len = chunk_lens[0];
+    for (i, &chunk_len) in chunk_lens.iter().enumerate().skip(1) {
+        len = merge_in_place(polymer, len, i * chunk_size, chunk_len, rule);
+    }
+    len
+}
+
+// Streaming //////////////////////////////////////////////////////////////////
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/*
+ The lengths of a polymer after reacting, both as is and with each unit type removed.
+*/
+#[allow(dead_code)]
+pub struct StreamedLengths {
+    pub full: usize,
+    pub without: Vec<(u8, usize)>, // Unit type (lowercase), and length with it removed
+}
+
+/*
+ Stream an ASCII polymer from a reader in fixed size chunks, reacting it as is and
+ with each of the given unit types (lowercase) removed in a single pass.
+
+ One reactor is kept per removed unit type, each of which skips units of that
+ type regardless of polarity. Whitespace is ignored.
+ Memory use is bounded by the reactor stacks, rather than the length of the polymer.
+*/
+#[allow(dead_code)]
+pub fn react_stream<Rd, R>(mut reader: Rd, rule: &R, unit_types: &[u8]) -> std::io::Result<StreamedLengths>
+    where Rd: std::io::Read,
+          R: ReactionRule<u8>
+{
+    let mut full = Reactor::new(rule);
+    let mut without = unit_types.iter()
+        .map(|&t| (t, Reactor::new(rule)))
+        .collect::<Vec<(u8, Reactor<u8, R>)>>();
+
+    let mut chunk = vec![0; CHUNK_SIZE];
+    loop {
+        let read = match reader.read(&mut chunk) {
+            Ok(0) => break, // End of stream
+            Ok(read) => read,
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+
+        for &unit in chunk[..read].iter().filter(|u| !u.is_ascii_whitespace()) {
+            full.push(unit);
+            let unit_type = unit.to_ascii_lowercase();
+            for (removed, reactor) in without.iter_mut() {
+                if unit_type != *removed { reactor.push(unit) }
+            }
+        }
+    }
+
+    Ok(StreamedLengths {
+        full: full.len(),
+        without: without.into_iter().map(|(t, r)| (t, r.len())).collect(),
+    })
+}
+
+// Trace //////////////////////////////////////////////////////////////////////
+
+/*
+ A single reaction between two units, identified by their position in the original polymer.
+
+ The pass is the round in which the units would react if every reactive pair reacted
+ simultaneously; i.e. one more than the deepest reaction which had to occur
+ between the two units before they became adjacent.
+*/
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TracedReaction {
+    pub left: usize,
+    pub right: usize,
+    pub pass: usize,
+    pub product: Option<u8>, // The product replaces the left unit (if any)
+}
+
+/*
+ A record of every reaction which occurred while reacting a polymer, and which units survived.
+*/
+pub struct Trace {
+    pub reactions: Vec<TracedReaction>,
+    pub survivors: Vec<(usize, u8)>, // Original position and unit
+}
+
+impl Trace {
+
+    /*
+     Get the number of passes required if every reactive pair reacted simultaneously.
+    */
+    #[allow(dead_code)]
+    pub fn passes(&self) -> usize {
+        self.reactions.iter().map(|r| r.pass).max().unwrap_or(0)
+    }
+
+    /*
+     Format every reaction in the order it occurred, one per line. e.g.
+         pass 2: 1 'a' + 4 'A' => annihilate
+    */
+    #[allow(dead_code)]
+    pub fn to_list(&self, polymer: &[u8]) -> String {
+        let mut out = String::new();
+        for r in &self.reactions {
+            let result = match r.product {
+                Some(product) => format!("'{}'", product as char),
+                None => String::from("annihilate"),
+            };
+            out += &format!("pass {}: {} '{}' + {} '{}' => {}\n", r.pass,
+                            r.left, polymer[r.left] as char, r.right, polymer[r.right] as char, result);
+        }
+        out
+    }
+
+    /*
+     Format the surviving units as runs which were contiguous in the original polymer,
+     each prefixed by its original position. e.g.
+         [0]dab [5]CBA [12]caDA
+    */
+    #[allow(dead_code)]
+    pub fn to_visualisation(&self) -> String {
+        let mut out = String::new();
+        let mut next = None;
+        for &(i, unit) in &self.survivors {
+            if next != Some(i) {
+                if next.is_some() { out.push(' ') }
+                out += &format!("[{}]", i);
+            }
+            out.push(unit as char);
+            next = Some(i + 1);
+        }
+        out
+    }
+}
+
+/*
+ Process polymer reactions until inert, recording every reaction.
+ Units are given along with their position in the original polymer.
+
+ This is the same stack-based algorithm as the Reactor, except each unit on the
+ stack also records the deepest pass of any reaction between it and the next unit.
+*/
+pub fn react_traced<I, R>(polymer: I, rule: &R) -> Trace
+    where I: IntoIterator<Item=(usize, u8)>,
+          R: ReactionRule<u8>
+{
+    let mut reactions = Vec::new();
+    let mut stack: Vec<(usize, u8, usize)> = Vec::new(); // Position, unit, deepest pass to the right
+
+    for (i, unit) in polymer {
+        let mut right = (i, unit);
+        loop {
+            let (left_i, left, inner_pass) = match stack.last() {
+                Some(&top) => top,
+                None => { stack.push((right.0, right.1, 0)); break }
+            };
+
+            let reaction = rule.react(left, right.1);
+            let product = match reaction {
+                Reaction::Inert => { stack.push((right.0, right.1, 0)); break },
+                Reaction::Annihilate => None,
+                Reaction::Product(product) => Some(product),
+            };
+
+            // Record reaction, which is now between the new top and the next unit
+            let pass = inner_pass + 1;
+            reactions.push(TracedReaction { left: left_i, right: right.0, pass, product });
+            stack.pop();
+            if let Some(top) = stack.last_mut() {
+                top.2 = top.2.max(pass);
+            }
+
+            match product {
+                Some(product) => right = (left_i, product),
+                None => break,
+            }
+        }
+    }
+
+    Trace {
+        reactions,
+        survivors: stack.into_iter().map(|(i, unit, _)| (i, unit)).collect(),
+    }
+}
+
+// Explanation ////////////////////////////////////////////////////////////////
+
+/*
+ A group of reactions unlocked by removing one or more units, which previously
+ separated the two sides of every reaction in the chain.
+*/
+#[allow(dead_code)]
+pub struct Chain {
+    pub removed: Vec<usize>,              // Positions of the removed units
+    pub reactions: Vec<TracedReaction>,   // Ordered from innermost to outermost
+}
+
+#[allow(dead_code)]
+impl Chain {
+
+    /*
+     Get the number of units in the reacted polymer which this chain eliminates.
+    */
+    pub fn units_eliminated(&self) -> usize {
+        self.removed.len() + self.reactions.len() * 2
+    }
+
+    /*
+     Format the units involved in the chain, with the removed units in brackets.
+     Long chains are abbreviated in the middle.
+    */
+    pub fn to_string(&self, polymer: &[u8]) -> String {
+        let mut positions = self.reactions.iter()
+            .flat_map(|r| vec![r.left, r.right])
+            .chain(self.removed.iter().cloned())
+            .collect::<Vec<usize>>();
+        positions.sort();
+
+        let unit = |i: &usize| {
+            let unit = polymer[*i] as char;
+            if self.removed.contains(i) { format!("[{}]", unit) } else { unit.to_string() }
+        };
+        if positions.len() <= 40 {
+            positions.iter().map(unit).collect()
+        } else {
+            let start = positions[..20].iter().map(unit).collect::<String>();
+            let end = positions[positions.len() - 20..].iter().map(unit).collect::<String>();
+            format!("{}...{}", start, end)
+        }
+    }
+}
+
+/*
+ An explanation of how much removing a unit type shortens a reacted polymer.
+*/
+#[allow(dead_code)]
+pub struct RemovalExplanation {
+    pub unit_type: u8,
+    pub removed: usize,       // Units of this type which survived the initial reaction
+    pub unlocked: usize,      // Reactions which only occur once they are removed
+    pub chains: Vec<Chain>,   // Ordered from most to fewest units eliminated
+}
+
+#[allow(dead_code)]
+impl RemovalExplanation {
+
+    /*
+     Get the number of units by which the reacted polymer is shortened.
+    */
+    pub fn shortened_by(&self) -> usize {
+        self.removed + self.unlocked * 2
+    }
+}
+
+/*
+ Explain how removing all units of a type (regardless of polarity) shortens a polymer.
+
+ Reacting a polymer first leaves only units which cannot react with their neighbours.
+ Removing units of a type then allows their two neighbours to meet, which may react,
+ in turn allowing their neighbours to meet and so on. Each chain of reactions
+ unlocked in this way is grouped by its outermost reaction, and must span one or
+ more removed units (otherwise it would have already occurred).
+*/
+#[allow(dead_code)]
+pub fn explain_removal<R: ReactionRule<u8>>(polymer: &[u8], unit_type: u8, rule: &R) -> RemovalExplanation {
+    let reacted = react_traced(polymer.iter().cloned().enumerate(), rule).survivors;
+    let (removed, remaining): (Vec<_>, Vec<_>) = reacted.into_iter()
+        .partition(|(_, u)| u.to_ascii_lowercase() == unit_type);
+    let removed = removed.into_iter().map(|(i, _)| i).collect::<Vec<usize>>();
+
+    // React again without the removed units
+    let mut unlocked = react_traced(remaining, rule).reactions;
+    let num_unlocked = unlocked.len();
+
+    // Group reactions by their outermost reaction
+    unlocked.sort_by_key(|r| (r.left, std::cmp::Reverse(r.right)));
+    let mut chains: Vec<(usize, usize, Vec<TracedReaction>)> = Vec::new();
+    for reaction in unlocked {
+        match chains.last_mut() {
+            Some((_, right, reactions)) if reaction.left < *right => reactions.push(reaction),
+            _ => chains.push((reaction.left, reaction.right, vec![reaction])),
+        }
+    }
+
+    let mut chains = chains.into_iter()
+        .map(|(left, right, mut reactions)| {
+            reactions.reverse(); // Innermost first
+            Chain {
+                removed: removed.iter().cloned().filter(|&i| left < i && i < right).collect(),
+                reactions,
+            }
+        })
+        .collect::<Vec<Chain>>();
+    chains.sort_by_key(|c| std::cmp::Reverse(c.units_eliminated()));
+
+    RemovalExplanation {
+        unit_type,
+        removed: removed.len(),
+        unlocked: num_unlocked,
+        chains,
+    }
+}
@@ -178,7 +178,7 @@ impl <'a,T> SparseVectorCursor<'a,T> {
      Get the mutable value at the current cursor position.
     */
     #[allow(dead_code)]
-    pub fn get_mut(&'a mut self) -> &'a mut T {
+    pub fn get_mut(&mut self) -> &mut T {
         &mut self.vec.elements[self.pos]
     }
 
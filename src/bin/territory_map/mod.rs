@@ -0,0 +1,133 @@
+
+use super::geometry::*;
+use super::image::*;
+
+// Colours ////////////////////////////////////////////////////////////////////
+
+const TIE: Colour = Colour { r: 40, g: 40, b: 40 };
+const INFINITE_FADE: Colour = Colour { r: 128, g: 128, b: 128 };
+const SITE: Colour = Colour::BLACK;
+#[allow(dead_code)]
+const SAFE_BOUNDARY: Colour = Colour::WHITE;
+
+/*
+ Pick a distinct colour for each site by stepping around the colour wheel by the golden angle,
+ so neighbouring indices never share similar hues.
+*/
+fn site_colour(site: usize) -> Colour {
+    let hue = (site as f64 * 137.508) % 360.0;
+    let (saturation, value) = (0.6, 0.9);
+
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match (hue / 60.0) as usize {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    let channel = |c: f64| ((c + m) * 255.0).round() as u8;
+    Colour::new(channel(r), channel(g), channel(b))
+}
+
+// Rendering //////////////////////////////////////////////////////////////////
+
+/*
+ A rendering of the territory closest to each site, one pixel per coordinate.
+
+ Coordinates are coloured by their closest site, and those tied between multiple sites
+ are shaded dark grey. Infinite regions are faded and hatched with diagonal stripes,
+ and each site is marked with a black square.
+*/
+pub struct TerritoryMap {
+    bound: Rect,
+    image: Image,
+}
+
+impl TerritoryMap {
+
+    /*
+     Render the territories of each site under a metric, within the given rectangle.
+     Which regions are infinite is taken from the given regions (one per site).
+    */
+    pub fn new<M: Metric>(sites: &[Point], regions: &[Region], metric: &M, bound: Rect) -> TerritoryMap {
+        let mut image = Image::new(bound.width(), bound.height(), TIE);
+
+        for y in bound.from.y..=bound.to.y {
+            for x in bound.from.x..=bound.to.x {
+                let owner = match closest_site(&Point::new(x, y), sites, metric) {
+                    Some(owner) => owner,
+                    None => continue,
+                };
+
+                let mut colour = site_colour(owner);
+                if regions[owner].infinite {
+                    colour = colour.lerp(INFINITE_FADE, 0.5);
+                    if (x + y).rem_euclid(8) < 2 {
+                        colour = colour.lerp(Colour::BLACK, 0.3);
+                    }
+                }
+                image.set((x - bound.from.x) as usize, (y - bound.from.y) as usize, colour);
+            }
+        }
+
+        let mut map = TerritoryMap { bound, image };
+        for site in sites {
+            map.fill(Point::new(site.x - 1, site.y - 1), 3, SITE);
+        }
+        map
+    }
+
+    /*
+     Outline the coordinates whose distances to all sites sum to no more than max_sum.
+     Coordinates within the region but next to one outside it are drawn white.
+    */
+    #[allow(dead_code)]
+    pub fn outline_region<M: Metric>(&mut self, sites: &[Point], metric: &M, max_sum: i64) {
+
+        // Find coordinates within region, including a border around the map
+        let outer = self.bound.expand(1);
+        let index = |p: Point| (p.y - outer.from.y) as usize * outer.width() + (p.x - outer.from.x) as usize;
+        let mut within = vec![false; outer.width() * outer.height()];
+        for y in outer.from.y..=outer.to.y {
+            for x in outer.from.x..=outer.to.x {
+                let point = Point::new(x, y);
+                within[index(point)] = sites.iter().map(|s| metric.dist(s, &point)).sum::<i64>() <= max_sum;
+            }
+        }
+
+        // Outline edge of region
+        let bound = self.bound;
+        for y in bound.from.y..=bound.to.y {
+            for x in bound.from.x..=bound.to.x {
+                let point = Point::new(x, y);
+                if !within[index(point)] { continue }
+
+                let neighbours = [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)];
+                if neighbours.iter().any(|&(nx, ny)| !within[index(Point::new(nx, ny))]) {
+                    self.fill(point, 1, SAFE_BOUNDARY);
+                }
+            }
+        }
+    }
+
+    /*
+     Fill a square of coordinates with its top-left corner at the given point.
+    */
+    fn fill(&mut self, from: Point, size: i32, colour: Colour) {
+        for y in from.y..(from.y + size) {
+            for x in from.x..(from.x + size) {
+                if self.bound.contains(&Point::new(x, y)) {
+                    self.image.set((x - self.bound.from.x) as usize, (y - self.bound.from.y) as usize, colour);
+                }
+            }
+        }
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+}
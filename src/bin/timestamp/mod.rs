@@ -0,0 +1,124 @@
+
+// Date ///////////////////////////////////////////////////////////////////////
+
+/*
+ A calendar date in the proleptic Gregorian calendar.
+ Fields are ordered so that the derived ordering is chronological.
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+
+    fn is_leap_year(year: i32) -> bool {
+        (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+    }
+
+    fn days_in_month(year: i32, month: u32) -> u32 {
+        match month {
+            2 if Date::is_leap_year(year) => 29,
+            2 => 28,
+            4 | 6 | 9 | 11 => 30,
+            _ => 31,
+        }
+    }
+
+    /*
+     Get the following day.
+    */
+    pub fn next_day(&self) -> Date {
+        if self.day < Date::days_in_month(self.year, self.month) {
+            Date { day: self.day + 1, ..*self }
+        } else if self.month < 12 {
+            Date { month: self.month + 1, day: 1, ..*self }
+        } else {
+            Date { year: self.year + 1, month: 1, day: 1 }
+        }
+    }
+}
+
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// Timestamp //////////////////////////////////////////////////////////////////
+
+/*
+ A point in time to the nearest minute.
+ Fields are ordered so that the derived ordering is chronological.
+*/
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    pub date: Date,
+    pub hour: u32,
+    pub minute: u32,
+}
+
+impl Timestamp {
+
+    /*
+     Parse a timestamp from a string in the following format:
+         [1518-11-01 23:58]
+    */
+    pub fn parse(timestamp: &str) -> Result<Timestamp, String> {
+        let invalid = || format!("Invalid timestamp: {}", timestamp);
+
+        let inner = timestamp.trim()
+            .strip_prefix('[')
+            .and_then(|t| t.strip_suffix(']'))
+            .ok_or_else(invalid)?;
+        let fields = inner
+            .split(['-', ' ', ':'])
+            .map(|f| f.parse::<i32>().map_err(|_| invalid()))
+            .collect::<Result<Vec<i32>, String>>()?;
+
+        if let [year, month, day, hour, minute] = fields[..] {
+            let timestamp = Timestamp {
+                date: Date { year, month: month as u32, day: day as u32 },
+                hour: hour as u32,
+                minute: minute as u32,
+            };
+
+            let valid_date = (1..=12).contains(&month) &&
+                (1..=Date::days_in_month(year, month as u32) as i32).contains(&day);
+            let valid_time = (0..24).contains(&hour) && (0..60).contains(&minute);
+            if valid_date && valid_time { Ok(timestamp) } else { Err(invalid()) }
+        } else {
+            Err(invalid())
+        }
+    }
+
+    /*
+     Get the date of the midnight hour this timestamp is closest to.
+     Times before noon belong to the same day, and times after to the next day.
+
+     e.g. a guard beginning their shift at 23:58 is on duty for the following midnight hour.
+    */
+    pub fn shift_date(&self) -> Date {
+        if self.hour < 12 { self.date } else { self.date.next_day() }
+    }
+
+    /*
+     Get the minute of the midnight hour (00:00 - 00:59) this timestamp occurs at.
+     Returns an error if the timestamp is outside the midnight hour.
+    */
+    pub fn midnight_minute(&self) -> Result<usize, String> {
+        if self.hour == 0 {
+            Ok(self.minute as usize)
+        } else {
+            Err(format!("Timestamp is outside the midnight hour: {}", self))
+        }
+    }
+}
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "[{} {:02}:{:02}]", self.date, self.hour, self.minute)
+    }
+}